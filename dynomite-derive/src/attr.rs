@@ -1,41 +1,365 @@
-//! dynomite field attributes
+//! dynomite field and container attributes
 
 use proc_macro_error::abort;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
-    Ident, LitStr, Token,
+    Ident, Lit, LitInt, LitStr, Token,
 };
 
 #[derive(Clone)]
 pub enum Attr {
     /// Denotes field should be replaced with Default impl when absent in ddb
     Default(Ident),
+    /// Denotes field should be replaced with the value of Lit when absent in ddb
+    DefaultValue(Ident, Lit),
+    /// Denotes field should be replaced with Default impl when absent or invalid in ddb
+    DefaultOnError(Ident),
+    /// Denotes field should be read, falling back to its Default impl when absent, but never written
+    ReadOnly(Ident),
+    /// Denotes field should be read as usual but never written
+    SkipSerializing(Ident),
+    /// Denotes field should be written but never read, always resolving its Default impl
+    SkipDeserializing(Ident),
     /// Denotes field should be renamed to value of ListStr
     Rename(Ident, LitStr),
+    /// Denotes fields without a rename should be renamed following the case convention of LitStr
+    RenameAll(Ident, LitStr),
+    /// Denotes fields without a rename should be written following the case convention of LitStr
+    RenameAllSerialize(Ident, LitStr),
+    /// Denotes fields without a rename should also be read following the case convention of LitStr
+    RenameAllDeserialize(Ident, LitStr),
+    /// Denotes field may also be read from an attribute named by the value of LitStr
+    Alias(Ident, LitStr),
+    /// Denotes field should be renamed to value of LitStr within the {Name}Key struct only
+    KeyRename(Ident, LitStr),
     /// Denotes Item partition (primary) key
     PartitionKey(Ident),
     /// Denotes Item sort key
     SortKey(Ident),
+    /// Denotes the type of a key field within the {Name}Key struct
+    KeyAs(Ident, LitStr),
+    /// Denotes the position of a sort key field within a composite sort key
+    Order(Ident, LitInt),
+    /// Denotes the attribute name of a composite sort key
+    SortKeyName(Ident, LitStr),
+    /// Denotes the separator joining the components of a composite sort key
+    SortKeySeparator(Ident, LitStr),
+    /// Denotes the delimiter joining the key fields of a `key_string`
+    KeyDelimiter(Ident, LitStr),
+    /// Denotes field attributes should be hoisted into the parent,
+    /// optionally prefixed with the value of LitStr
+    Flatten(Ident, Option<LitStr>),
+    /// Denotes a flatten field whose type's Item impl provides the parent Item's key
+    Key(Ident),
+    /// Denotes field should be encoded as a nested M map
+    Map(Ident),
+    /// Denotes field should be stored as a TTL attribute of epoch seconds
+    Ttl(Ident),
+    /// Denotes field should be stored as fractional epoch seconds
+    Epoch(Ident),
+    /// Denotes field should also be read from numbers stored as `S` strings
+    LenientNumber(Ident),
+    /// Denotes field should be compressed with the algorithm named by LitStr
+    Compress(Ident, LitStr),
+    /// Denotes field should be stored as a JSON string
+    Json(Ident),
+    /// Denotes field should be encrypted and decrypted with the module at the path of LitStr
+    EncryptWith(Ident, LitStr),
+    /// Denotes field should be stored as NULL when it equals its Default
+    NullWhenDefault(Ident),
+    /// Denotes Item should not generate a {Name}Key struct
+    SkipKeyStruct(Ident),
+    /// Denotes Item should not generate a {Name}Query builder
+    SkipQueryStruct(Ident),
+    /// Denotes Item should not generate per-field scan filter functions
+    SkipScanFilters(Ident),
+    /// Denotes Item should convert into the existing key struct at the path of LitStr
+    /// rather than generating a {Name}Key struct
+    KeyStruct(Ident, LitStr),
+    /// Denotes a `Default` impl should be generated
+    DeriveDefault(Ident),
+    /// Denotes an `into_ordered_attributes` method should be generated
+    Ordered(Ident),
+    /// Denotes a `changed_fields` method should be generated
+    ChangedFields(Ident),
+    /// Denotes a `merge` method should be generated
+    Merge(Ident),
+    /// Denotes the {Name}Key struct should also derive `Eq` and `Hash`
+    KeyHash(Ident),
+    /// Denotes Item should generate a {Name}Update struct of optional non-key fields
+    UpdateStruct(Ident),
+    /// Denotes a single field struct should delegate to its field's Attribute impl
+    Transparent(Ident),
+    /// Denotes enum variant names should be matched ignoring ASCII case when parsing
+    CaseInsensitive(Ident),
+    /// Denotes an enum variant which unrecognized values are parsed as
+    Other(Ident),
+    /// Denotes enum variant names should be stored under the attribute named by LitStr
+    Tag(Ident, LitStr),
+    /// Denotes tagged enum variant fields should be stored under the attribute named by LitStr
+    Content(Ident, LitStr),
+    /// Denotes enum variant names should be stored prefixed with the value of LitStr
+    Prefix(Ident, LitStr),
+    /// Denotes prefixed enum variant names should also be parsed without their prefix
+    Lenient(Ident),
+    /// Denotes a `Display` impl writing each variant's stored name should be generated
+    Display(Ident),
+    /// Denotes a `FromStr` impl parsing each variant's stored name should be generated
+    FromStr(Ident),
+    /// Denotes an `as_str` method and `AsRef<str>` impl returning each variant's stored name should be generated
+    AsStr(Ident),
+    /// Denotes the name of the table Item is stored in
+    Table(Ident, LitStr),
+    /// Denotes Attributes are nested within a single `M` attribute named by the value of LitStr
+    Wrapped(Ident, LitStr),
+    /// Denotes Item should generate async `get`, `save` and `delete` methods
+    Crud(Ident),
+    /// Denotes generated code should refer to the dynomite crate by the path of LitStr
+    Crate(Ident, LitStr),
+    /// Denotes attributes left over after resolving all fields should be an error
+    DenyUnknownFields(Ident),
+    /// Denotes an integer field used for optimistic locking
+    Version(Ident),
+    /// Denotes field should be assigned the current time when unset at serialization time
+    CreatedAt(Ident),
+    /// Denotes field should be assigned the current time at serialization time
+    UpdatedAt(Ident),
+    /// Denotes the presence of a default or optional field's attribute should be recorded
+    TrackPresence(Ident),
+    /// Denotes a `HashSet<&'static str>` field recording which tracked attributes were present
+    Presence(Ident),
+}
+
+/// The case conventions supported by `rename_all`
+pub const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// The names of all known dynomite attributes, used to suggest corrections for typos
+const NAMES: &[&str] = &[
+    "default",
+    "default_value",
+    "default_on_error",
+    "read_only",
+    "skip_serializing",
+    "skip_deserializing",
+    "rename",
+    "rename_all",
+    "rename_all_serialize",
+    "rename_all_deserialize",
+    "key_rename",
+    "alias",
+    "partition_key",
+    "sort_key",
+    "key_as",
+    "order",
+    "sort_key_name",
+    "sort_key_separator",
+    "key_delimiter",
+    "flatten",
+    "key",
+    "map",
+    "ttl",
+    "epoch",
+    "lenient_number",
+    "compress",
+    "json",
+    "encrypt_with",
+    "null_when_default",
+    "skip_key_struct",
+    "skip_query_struct",
+    "skip_scan_filters",
+    "key_struct",
+    "derive_default",
+    "ordered",
+    "changed_fields",
+    "merge",
+    "key_hash",
+    "update_struct",
+    "transparent",
+    "case_insensitive",
+    "other",
+    "tag",
+    "content",
+    "prefix",
+    "lenient",
+    "display",
+    "from_str",
+    "as_str",
+    "table",
+    "wrapped",
+    "crud",
+    "crate",
+    "deny_unknown_fields",
+    "version",
+    "created_at",
+    "updated_at",
+    "track_presence",
+    "presence",
+];
+
+/// Aborts with an error for an unknown attribute, suggesting the closest known name
+fn unknown(name: &Ident) -> ! {
+    let name_str = name.to_string();
+    let suggestion = NAMES
+        .iter()
+        .map(|known| (levenshtein(&name_str, known), known))
+        .min()
+        .filter(|(distance, _)| *distance <= (name_str.len() / 3).max(1));
+    match suggestion {
+        Some((_, known)) => abort!(
+            name,
+            "unexpected dynomite attribute: {}. Did you mean `{}`?",
+            name_str,
+            known
+        ),
+        None => abort!(name, "unexpected dynomite attribute: {}", name_str),
+    }
+}
+
+/// The number of single character edits needed to turn `a` into `b`
+fn levenshtein(
+    a: &str,
+    b: &str,
+) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + if a == *b { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
 }
 
 impl Parse for Attr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         use self::Attr::*;
-        let name: Ident = input.parse()?;
+        // `crate` is a keyword
+        let name = input.call(Ident::parse_any)?;
         let name_str = name.to_string();
         if input.peek(Token![=]) {
             // `name = value` attributes.
             let assign = input.parse::<Token![=]>()?; // skip '='
+            if name_str == "default_value" {
+                let lit = input.parse::<Lit>()?;
+                if !matches!(
+                    lit,
+                    Lit::Str(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_)
+                ) {
+                    abort!(
+                        lit,
+                        "dynomite default_value values must be a string, integer, float or bool literal"
+                    );
+                }
+                return Ok(DefaultValue(name, lit));
+            }
             if input.peek(LitStr) {
                 let lit: LitStr = input.parse()?;
                 match &*name_str {
-                    "rename" => Ok(Rename(name, lit)),
-                    unsupported => abort! {
-                        name,
-                        "unsupported dynomite {} attribute",
-                        unsupported
-                    },
+                    "key_as" => {
+                        if lit.parse::<syn::Type>().is_err() {
+                            abort!(lit, "dynomite key_as values must be a type");
+                        }
+                        Ok(KeyAs(name, lit))
+                    }
+                    "encrypt_with" => {
+                        if lit.parse::<syn::Path>().is_err() {
+                            abort!(lit, "dynomite encrypt_with values must be a module path");
+                        }
+                        Ok(EncryptWith(name, lit))
+                    }
+                    "table" => Ok(Table(name, lit)),
+                    "wrapped" => Ok(Wrapped(name, lit)),
+                    "key_struct" => {
+                        if lit.parse::<syn::Path>().is_err() {
+                            abort!(lit, "dynomite key_struct values must be a type path");
+                        }
+                        Ok(KeyStruct(name, lit))
+                    }
+                    "tag" => Ok(Tag(name, lit)),
+                    "content" => Ok(Content(name, lit)),
+                    "prefix" => Ok(Prefix(name, lit)),
+                    "crate" => {
+                        if lit.parse::<syn::Path>().is_err() {
+                            abort!(lit, "dynomite crate values must be a path");
+                        }
+                        Ok(Crate(name, lit))
+                    }
+                    "rename_all" | "rename_all_serialize" | "rename_all_deserialize" => {
+                        if !RENAME_RULES.contains(&lit.value().as_str()) {
+                            abort!(
+                                lit,
+                                "dynomite {} values must be one of {}",
+                                name_str,
+                                RENAME_RULES.join(", ")
+                            );
+                        }
+                        match name_str.as_ref() {
+                            "rename_all_serialize" => Ok(RenameAllSerialize(name, lit)),
+                            "rename_all_deserialize" => Ok(RenameAllDeserialize(name, lit)),
+                            _ => Ok(RenameAll(name, lit)),
+                        }
+                    }
+                    "sort_key_name" => Ok(SortKeyName(name, lit)),
+                    "sort_key_separator" => {
+                        if lit.value().is_empty() {
+                            abort!(lit, "dynomite sort_key_separator values may not be empty");
+                        }
+                        Ok(SortKeySeparator(name, lit))
+                    }
+                    "key_delimiter" => {
+                        if lit.value().is_empty() || lit.value().contains('\\') {
+                            abort!(
+                                lit,
+                                "dynomite key_delimiter values may not be empty or contain `\\`"
+                            );
+                        }
+                        Ok(KeyDelimiter(name, lit))
+                    }
+                    "rename" | "key_rename" | "alias" => {
+                        let value = lit.value();
+                        if value.trim().is_empty() {
+                            abort!(lit, "dynomite {} values may not be empty", name_str);
+                        }
+                        if value.trim() != value {
+                            abort!(
+                                lit,
+                                "dynomite {} values may not have leading or trailing whitespace",
+                                name_str
+                            );
+                        }
+                        match &*name_str {
+                            "rename" => Ok(Rename(name, lit)),
+                            "alias" => Ok(Alias(name, lit)),
+                            _ => Ok(KeyRename(name, lit)),
+                        }
+                    }
+                    "compress" => {
+                        if lit.value() != "gzip" {
+                            abort! {
+                                lit,
+                                "unsupported dynomite compression algorithm {}",
+                                lit.value()
+                            };
+                        }
+                        Ok(Compress(name, lit))
+                    }
+                    _ => unknown(&name),
                 }
+            } else if input.peek(LitInt) && name_str == "order" {
+                Ok(Order(name, input.parse()?))
             } else {
                 abort! {
                     assign,
@@ -44,15 +368,88 @@ impl Parse for Attr {
             }
         } else if input.peek(syn::token::Paren) {
             // `name(...)` attributes.
-            abort!(name, "unexpected dynomite attribute: {}", name_str);
+            let content;
+            syn::parenthesized!(content in input);
+            match name_str.as_ref() {
+                "flatten" => {
+                    let option: Ident = content.parse()?;
+                    if option != "prefix" {
+                        abort!(option, "unexpected dynomite flatten option: {}", option);
+                    }
+                    content.parse::<Token![=]>()?;
+                    Ok(Flatten(name, Some(content.parse()?)))
+                }
+                _ => unknown(&name),
+            }
         } else {
             // Attributes represented with a sole identifier.
             match name_str.as_ref() {
                 "default" => Ok(Default(name)),
+                "default_on_error" => Ok(DefaultOnError(name)),
+                "read_only" => Ok(ReadOnly(name)),
+                "skip_serializing" => Ok(SkipSerializing(name)),
+                "skip_deserializing" => Ok(SkipDeserializing(name)),
                 "partition_key" => Ok(PartitionKey(name)),
                 "sort_key" => Ok(SortKey(name)),
-                _ => abort!(name, "unexpected dynomite attribute: {}", name_str),
+                "flatten" => Ok(Flatten(name, None)),
+                "key" => Ok(Key(name)),
+                "map" => Ok(Map(name)),
+                "json" => Ok(Json(name)),
+                "ttl" => Ok(Ttl(name)),
+                "epoch" => Ok(Epoch(name)),
+                "lenient_number" => Ok(LenientNumber(name)),
+                "null_when_default" => Ok(NullWhenDefault(name)),
+                "skip_key_struct" => Ok(SkipKeyStruct(name)),
+                "skip_query_struct" => Ok(SkipQueryStruct(name)),
+                "skip_scan_filters" => Ok(SkipScanFilters(name)),
+                "changed_fields" => Ok(ChangedFields(name)),
+                "merge" => Ok(Merge(name)),
+                "ordered" => Ok(Ordered(name)),
+                "derive_default" => Ok(DeriveDefault(name)),
+                "key_hash" => Ok(KeyHash(name)),
+                "update_struct" => Ok(UpdateStruct(name)),
+                "transparent" => Ok(Transparent(name)),
+                "case_insensitive" => Ok(CaseInsensitive(name)),
+                "other" => Ok(Other(name)),
+                "lenient" => Ok(Lenient(name)),
+                "display" => Ok(Display(name)),
+                "from_str" => Ok(FromStr(name)),
+                "as_str" => Ok(AsStr(name)),
+                "crud" => Ok(Crud(name)),
+                "deny_unknown_fields" => Ok(DenyUnknownFields(name)),
+                "version" => Ok(Version(name)),
+                "created_at" => Ok(CreatedAt(name)),
+                "updated_at" => Ok(UpdatedAt(name)),
+                "track_presence" => Ok(TrackPresence(name)),
+                "presence" => Ok(Presence(name)),
+                _ => unknown(&name),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Attr, NAMES};
+
+    #[test]
+    fn names_are_parsed() {
+        // rejected values abort, which panics outside of a proc macro
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let unparsed = NAMES
+            .iter()
+            .filter(|name| {
+                !["", " = \"camelCase\"", " = \"gzip\"", " = 1"]
+                    .iter()
+                    .any(|value| {
+                        let input = format!("{}{}", name, value);
+                        std::panic::catch_unwind(|| syn::parse_str::<Attr>(&input))
+                            .map_or(false, |attr| attr.is_ok())
+                    })
+            })
+            .collect::<Vec<_>>();
+        std::panic::set_hook(hook);
+        assert!(unparsed.is_empty(), "unparsed names: {:?}", unparsed);
+    }
+}
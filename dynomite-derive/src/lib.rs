@@ -36,12 +36,13 @@ use attr::Attr;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::ResultExt;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
     punctuated::Punctuated,
     Attribute,
     Data::{Enum, Struct},
-    DataStruct, DeriveInput, Field, Fields, Ident, Token, Variant, Visibility,
+    DataEnum, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Lit, LitInt, LitStr, Token,
+    Type, Variant, Visibility,
 };
 
 /// A Field and all its extracted dynomite derive attrs
@@ -69,10 +70,232 @@ impl<'a> ItemField<'a> {
             .any(|attr| matches!(attr, Attr::SortKey(_)))
     }
 
+    /// The type of this field within the {Name}Key struct, if it differs
+    fn key_as(&self) -> Option<(&LitStr, Type)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::KeyAs(_, lit) => Some((lit, lit.parse().expect("validated when parsed"))),
+            _ => None,
+        })
+    }
+
+    fn order(&self) -> Option<&LitInt> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Order(_, order) => Some(order),
+            _ => None,
+        })
+    }
+
     fn is_default_when_absent(&self) -> bool {
+        self.attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                Attr::Default(_)
+                    | Attr::DefaultValue(..)
+                    | Attr::DefaultOnError(_)
+                    | Attr::ReadOnly(_)
+            )
+        })
+    }
+
+    fn default_value(&self) -> Option<&Lit> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::DefaultValue(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    fn read_only(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::ReadOnly(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn skip_serializing(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::SkipSerializing(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    /// Returns the attribute of fields which are read but never written, if any
+    fn never_written(&self) -> Option<&Ident> {
+        self.read_only().or_else(|| self.skip_serializing())
+    }
+
+    fn skip_deserializing(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::SkipDeserializing(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn is_default_on_error(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::DefaultOnError(_)))
+    }
+
+    fn is_option(&self) -> bool {
+        is_option(&self.field.ty)
+    }
+
+    fn is_phantom_data(&self) -> bool {
+        is_phantom_data(&self.field.ty)
+    }
+
+    fn is_presence(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::Presence(_)))
+    }
+
+    fn track_presence(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::TrackPresence(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    /// Fields which are never stored
+    fn is_skipped(&self) -> bool {
+        self.is_phantom_data() || self.is_presence()
+    }
+
+    fn is_flatten(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::Flatten(..)))
+    }
+
+    fn map(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Map(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn ttl(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Ttl(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn epoch(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Epoch(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn lenient_number(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::LenientNumber(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn compress(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Compress(ident, _) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn null_when_default(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::NullWhenDefault(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn key_delegate(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Key(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn is_json(&self) -> bool {
+        self.json().is_some()
+    }
+
+    fn json(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Json(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn encrypt_with(&self) -> Option<(&Ident, syn::Path)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::EncryptWith(ident, lit) => {
+                Some((ident, lit.parse().expect("validated when parsed")))
+            }
+            _ => None,
+        })
+    }
+
+    fn flatten_prefix(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Flatten(_, Some(prefix)) => Some(prefix.value()),
+            _ => None,
+        })
+    }
+
+    fn is_created_at(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::CreatedAt(_)))
+    }
+
+    fn is_updated_at(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::UpdatedAt(_)))
+    }
+
+    fn version(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Version(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn key_rename(&self) -> Option<(&Ident, &LitStr)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::KeyRename(ident, lit) => Some((ident, lit)),
+            _ => None,
+        })
+    }
+
+    /// The `#[cfg(...)]` attributes of this field, repeated on the code generated for it
+    /// so that conditionally compiled fields are only referred to when they exist
+    fn cfg_attrs(&self) -> Vec<&Attribute> {
+        self.field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .collect()
+    }
+
+    /// The additional attribute names this field may be read from, in declaration order
+    fn aliases(&self) -> Vec<&LitStr> {
         self.attrs
             .iter()
-            .any(|attr| matches!(attr, Attr::Default(_)))
+            .filter_map(|attr| match attr {
+                Attr::Alias(_, lit) => Some(lit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The name of this key field's attribute within the {Name}Key struct and `Item::key`
+    fn key_deser_name(&self) -> String {
+        self.key_rename()
+            .map(|(_, lit)| lit.value())
+            .unwrap_or_else(|| self.deser_name())
     }
 
     fn deser_name(&self) -> String {
@@ -93,397 +316,4312 @@ impl<'a> ItemField<'a> {
     }
 }
 
-fn parse_attrs(all_attrs: &[Attribute]) -> Vec<Attr> {
-    all_attrs
+/// The item fields of a struct, renaming named fields stored under their own attribute
+/// name without a `rename` of their own following the container's `rename_all` conventions,
+/// if any
+fn item_fields<'a>(
+    container: &Container,
+    fields: &'a [Field],
+) -> Vec<ItemField<'a>> {
+    fields
         .iter()
-        .filter(|attr| attr.path.is_ident("dynomite"))
-        .flat_map(|attr| {
-            attr.parse_args_with(Punctuated::<Attr, Token![,]>::parse_terminated)
-                .unwrap_or_abort()
+        .map(|field| {
+            let mut item_field = ItemField::new(field);
+            let is_renamed = item_field
+                .attrs
+                .iter()
+                .any(|attr| matches!(attr, Attr::Rename(..)));
+            // composite sort key components are stored under the composite's name
+            let is_unnamed = item_field.is_skipped()
+                || item_field.is_flatten()
+                || (item_field.is_sort_key() && item_field.order().is_some());
+            let ident = match (&field.ident, is_renamed || is_unnamed) {
+                (Some(ident), false) => ident,
+                _ => return item_field,
+            };
+            let field_name = ident.to_string();
+            let serialized = match container.rename_all_serialize() {
+                Some(rule) => {
+                    let name = rename_all(&rule.value(), &field_name);
+                    item_field.attrs.push(Attr::Rename(
+                        Ident::new("rename", rule.span()),
+                        LitStr::new(&name, ident.span()),
+                    ));
+                    name
+                }
+                None => field_name.clone(),
+            };
+            // attributes written by another convention remain readable as aliases
+            if let Some(rule) = container.rename_all_deserialize() {
+                let name = rename_all(&rule.value(), &field_name);
+                if name != serialized {
+                    item_field.attrs.push(Attr::Alias(
+                        Ident::new("alias", rule.span()),
+                        LitStr::new(&name, ident.span()),
+                    ));
+                }
+            }
+            item_field
         })
         .collect()
 }
 
-/// Derives `dynomite::Item` type for struts with named fields
-///
-/// # Attributes
-///
-/// * `#[dynomite(partition_key)]` - required attribute, expected to be applied the target [partition attribute](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.PrimaryKey) field with an derivable DynamoDB attribute value of String, Number or Binary
-/// * `#[dynomite(sort_key)]` - optional attribute, may be applied to one target [sort attribute](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.SecondaryIndexes) field with an derivable DynamoDB attribute value of String, Number or Binary
-/// * `#[dynomite(rename = "actualName")]` - optional attribute, may be applied any item attribute field, useful when the DynamoDB table you're interfacing with has attributes whose names don't following Rust's naming conventions
-///
-/// # Panics
-///
-/// This proc macro will panic when applied to other types
-#[proc_macro_error::proc_macro_error]
-#[proc_macro_derive(Item, attributes(partition_key, sort_key, dynomite))]
-pub fn derive_item(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input);
-
-    let gen = match expand_item(ast) {
-        Ok(g) => g,
-        Err(e) => return e.to_compile_error().into(),
+/// Converts a snake_case field name to the case convention of a `rename_all` rule
+fn rename_all(
+    rule: &str,
+    name: &str,
+) -> String {
+    let words = name.split('_').filter(|word| !word.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
     };
+    match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => words.map(capitalize).collect(),
+        "camelCase" => words
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.to_string()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
 
-    gen.into_token_stream().into()
+/// Rejects aliases of fields which are not stored under a name of their own and
+/// attribute names, including aliases, declared by more than one field
+fn validate_attribute_names(
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> syn::Result<()> {
+    use syn::spanned::Spanned as _;
+    let mut names = std::collections::HashSet::new();
+    for field in fields {
+        let aliases = field.aliases();
+        let unnamed = field.is_skipped()
+            || field.is_flatten()
+            || composite
+                .map(|composite| composite.contains(field))
+                .unwrap_or(false);
+        if unnamed {
+            if let Some(alias) = aliases.first() {
+                return Err(syn::Error::new(
+                    alias.span(),
+                    "aliases may only be applied to fields stored under their own attribute name",
+                ));
+            }
+            continue;
+        }
+        if !names.insert(field.deser_name()) {
+            return Err(syn::Error::new(
+                field.field.span(),
+                format!(
+                    "attribute name {:?} is declared more than once",
+                    field.deser_name()
+                ),
+            ));
+        }
+        for alias in aliases {
+            if !names.insert(alias.value()) {
+                return Err(syn::Error::new(
+                    alias.span(),
+                    format!(
+                        "attribute name {:?} is declared more than once",
+                        alias.value()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
 }
 
-/// similar in spirit to `#[derive(Item)]` except these are exempt from declaring
-/// partition and sort keys
-#[proc_macro_error::proc_macro_error]
-#[proc_macro_derive(Attributes, attributes(dynomite))]
-pub fn derive_attributes(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input);
+// removes a field's attribute from a mutable `attrs` attribute map along with any of its
+// aliases, preferring its primary name
+//
+// {
+//   let value = attrs.remove("field_deser_name");
+//   let value = value.or(attrs.remove("alias"));
+//   value
+// }
+fn get_field_removal(field: &ItemField) -> proc_macro2::TokenStream {
+    let field_deser_name = field.deser_name();
+    let aliases = field.aliases();
+    if aliases.is_empty() {
+        return quote!(attrs.remove(#field_deser_name));
+    }
+    quote! {
+        {
+            let value = attrs.remove(#field_deser_name);
+            #(let value = value.or(attrs.remove(#aliases));)*
+            value
+        }
+    }
+}
 
-    let gen = match expand_attributes(ast) {
-        Ok(g) => g,
-        Err(e) => return e.to_compile_error().into(),
-    };
+/// Rejects fields which declare conflicting encodings
+fn validate_encoding(field: &ItemField) -> syn::Result<()> {
+    let encodings = field
+        .attrs
+        .iter()
+        .filter_map(|attr| match attr {
+            Attr::Flatten(ident, _)
+            | Attr::Map(ident)
+            | Attr::Ttl(ident)
+            | Attr::Epoch(ident)
+            | Attr::LenientNumber(ident)
+            | Attr::Compress(ident, _)
+            | Attr::Json(ident) => Some(ident),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if let [first, second, ..] = encodings.as_slice() {
+        return Err(syn::Error::new(
+            first.span(),
+            format!("{} and {} are mutually exclusive", first, second),
+        ));
+    }
+    if let Some(null_when_default) = field.null_when_default() {
+        if field.is_flatten() {
+            return Err(syn::Error::new(
+                null_when_default.span(),
+                "null_when_default may not be applied to flatten fields, which are not stored as a single attribute",
+            ));
+        }
+    }
+    if let Some((encrypt_with, _)) = field.encrypt_with() {
+        if field.is_flatten() {
+            return Err(syn::Error::new(
+                encrypt_with.span(),
+                "flatten fields may not be encrypted, encrypt the fields of the flattened type instead",
+            ));
+        }
+    }
+    if let Some(map) = field.map() {
+        if field.is_option() {
+            return Err(syn::Error::new(
+                map.span(),
+                "map fields may not be optional",
+            ));
+        }
+    }
+    Ok(())
+}
 
-    gen.into_token_stream().into()
+/// Returns true if the type is syntactically an `Option<...>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
 }
 
-/// Derives `dynomite::Attribute` for enum types
-///
-/// # Panics
-///
-/// This proc macro will panic when applied to other types
-#[proc_macro_error::proc_macro_error]
-#[proc_macro_derive(Attribute)]
-pub fn derive_attribute(input: TokenStream) -> TokenStream {
-    let ast = syn::parse_macro_input!(input);
-    let gen = expand_attribute(ast);
-    gen.into_token_stream().into()
+/// Returns true if the type is syntactically a tuple, array other than `[u8; N]`, slice or a
+/// collection other than `Vec<u8>`, none of which may be stored as an `S`, `N` or `B` key attribute
+fn is_non_scalar(ty: &Type) -> bool {
+    match ty {
+        Type::Array(array) => !is_u8(&array.elem),
+        Type::Tuple(_) | Type::Slice(_) => true,
+        Type::Reference(reference) => is_non_scalar(&reference.elem),
+        Type::Paren(paren) => is_non_scalar(&paren.elem),
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "Vec" => !is_vec_of_u8(Some(segment)),
+            Some(segment) => ["VecDeque", "HashMap", "BTreeMap", "HashSet", "BTreeSet"]
+                .iter()
+                .any(|name| segment.ident == name),
+            None => false,
+        },
+        _ => false,
+    }
 }
 
-fn expand_attribute(ast: DeriveInput) -> impl ToTokens {
-    let name = &ast.ident;
-    match ast.data {
-        Enum(variants) => {
-            make_dynomite_attr(name, &variants.variants.into_iter().collect::<Vec<_>>())
+/// Returns true if the type is syntactically a `HashMap<...>` or `BTreeMap<...>`
+fn is_string_map(ty: &Type) -> bool {
+    match ty {
+        Type::Paren(paren) => is_string_map(&paren.elem),
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "HashMap" || segment.ident == "BTreeMap")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is syntactically a `PhantomData<...>`
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "PhantomData")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is syntactically one of Rust's primitive integer types
+fn is_integer(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .map(|ident| {
+                [
+                    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+                    "isize",
+                ]
+                .iter()
+                .any(|name| ident == name)
+            })
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// A struct or enum and all its extracted dynomite derive attrs
+struct Container {
+    attrs: Vec<Attr>,
+}
+
+impl Container {
+    fn new(attrs: &[Attribute]) -> Self {
+        let attrs = parse_attrs(attrs);
+        Self { attrs }
+    }
+
+    fn skip_key_struct(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::SkipKeyStruct(_)))
+    }
+
+    fn skip_query_struct(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::SkipQueryStruct(_)))
+    }
+
+    fn skip_scan_filters(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::SkipScanFilters(_)))
+    }
+
+    fn key_hash(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::KeyHash(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn sort_key_name(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::SortKeyName(_, lit) => Some(lit.value()),
+            _ => None,
+        })
+    }
+
+    fn sort_key_separator(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::SortKeySeparator(_, lit) => Some(lit.value()),
+            _ => None,
+        })
+    }
+
+    fn key_delimiter(&self) -> String {
+        self.attrs
+            .iter()
+            .find_map(|attr| match attr {
+                Attr::KeyDelimiter(_, lit) => Some(lit.value()),
+                _ => None,
+            })
+            .unwrap_or_else(|| "#".into())
+    }
+
+    fn rename_all(&self) -> Option<&LitStr> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::RenameAll(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    /// The convention fields are written by, `rename_all_serialize` overriding `rename_all`
+    fn rename_all_serialize(&self) -> Option<&LitStr> {
+        self.attrs
+            .iter()
+            .find_map(|attr| match attr {
+                Attr::RenameAllSerialize(_, lit) => Some(lit),
+                _ => None,
+            })
+            .or_else(|| self.rename_all())
+    }
+
+    /// The convention fields are read by, `rename_all_deserialize` overriding `rename_all`
+    fn rename_all_deserialize(&self) -> Option<&LitStr> {
+        self.attrs
+            .iter()
+            .find_map(|attr| match attr {
+                Attr::RenameAllDeserialize(_, lit) => Some(lit),
+                _ => None,
+            })
+            .or_else(|| self.rename_all())
+    }
+
+    fn key_struct(&self) -> Option<(&LitStr, syn::Path)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::KeyStruct(_, lit) => Some((lit, lit.parse().expect("validated when parsed"))),
+            _ => None,
+        })
+    }
+
+    fn crate_path(&self) -> Option<(&LitStr, syn::Path)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Crate(_, lit) => Some((lit, lit.parse().expect("validated when parsed"))),
+            _ => None,
+        })
+    }
+
+    /// Replaces each `::dynomite` path prefix generated for this container with its
+    /// `#[dynomite(crate = "...")]` path, if any
+    fn with_crate_path(
+        &self,
+        tokens: impl ToTokens,
+    ) -> proc_macro2::TokenStream {
+        match self.crate_path() {
+            Some((_, path)) => replace_crate_path(tokens.into_token_stream(), &path),
+            None => tokens.into_token_stream(),
+        }
+    }
+
+    fn table(&self) -> Option<&LitStr> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Table(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    fn wrapped(&self) -> Option<(&Ident, &LitStr)> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Wrapped(ident, lit) => Some((ident, lit)),
+            _ => None,
+        })
+    }
+
+    fn crud(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Crud(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn update_struct(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::UpdateStruct(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn derive_default(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::DeriveDefault(_)))
+    }
+
+    fn ordered(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::Ordered(_)))
+    }
+
+    fn changed_fields(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::ChangedFields(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn merge(&self) -> bool {
+        self.attrs.iter().any(|attr| matches!(attr, Attr::Merge(_)))
+    }
+
+    fn deny_unknown_fields(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::DenyUnknownFields(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn transparent(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::Transparent(_)))
+    }
+
+    fn display(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::Display(_)))
+    }
+
+    fn as_str(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::AsStr(ident) => Some(ident),
+            _ => None,
+        })
+    }
+
+    fn from_str(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::FromStr(_)))
+    }
+
+    fn case_insensitive(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| matches!(attr, Attr::CaseInsensitive(_)))
+    }
+
+    fn tag(&self) -> Option<&LitStr> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Tag(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    fn content(&self) -> Option<&LitStr> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Content(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    fn prefix(&self) -> Option<&LitStr> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Prefix(_, lit) => Some(lit),
+            _ => None,
+        })
+    }
+
+    fn lenient(&self) -> Option<&Ident> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attr::Lenient(ident) => Some(ident),
+            _ => None,
+        })
+    }
+}
+
+/// Sort key fields joined in `order` into a single attribute
+struct CompositeSortKey<'a> {
+    name: String,
+    separator: String,
+    fields: Vec<&'a ItemField<'a>>,
+}
+
+impl<'a> CompositeSortKey<'a> {
+    /// Resolves the composite sort key of `fields`, if more than one sort key field is declared
+    fn new(
+        container: &Container,
+        fields: &'a [ItemField<'a>],
+    ) -> syn::Result<Option<Self>> {
+        use syn::spanned::Spanned as _;
+        if let Some(order) = fields
+            .iter()
+            .find_map(|field| field.order().filter(|_| !field.is_sort_key()))
+        {
+            return Err(syn::Error::new(
+                order.span(),
+                "order may only be applied to sort_key fields",
+            ));
+        }
+        let sort_keys = fields
+            .iter()
+            .filter(|field| field.is_sort_key())
+            .collect::<Vec<_>>();
+        if sort_keys.iter().all(|field| field.order().is_none()) {
+            return match sort_keys.as_slice() {
+                [_, field, ..] => Err(syn::Error::new(
+                    field.field.span(),
+                    "multiple sort_key fields must each declare their `order`",
+                )),
+                _ => Ok(None),
+            };
+        }
+
+        let mut ordered = Vec::new();
+        for field in sort_keys {
+            let order = match field.order() {
+                Some(order) => order,
+                None => {
+                    return Err(syn::Error::new(
+                        field.field.span(),
+                        "multiple sort_key fields must each declare their `order`",
+                    ))
+                }
+            };
+            if field.is_flatten()
+                || field.map().is_some()
+                || field.ttl().is_some()
+                || field.epoch().is_some()
+                || field.lenient_number().is_some()
+                || field.compress().is_some()
+                || field.is_json()
+            {
+                return Err(syn::Error::new(
+                    order.span(),
+                    "composite sort key fields are stored using their `ToString` and `FromStr` impls and may not declare other encodings",
+                ));
+            }
+            ordered.push((order.base10_parse::<usize>()?, order, field));
+        }
+        ordered.sort_by_key(|(index, ..)| *index);
+        if let Some((_, order, _)) = ordered
+            .iter()
+            .enumerate()
+            .find(|(position, (index, ..))| position != index)
+            .map(|(_, ordered)| ordered)
+        {
+            return Err(syn::Error::new(
+                order.span(),
+                "sort_key orders must be unique and contiguous, starting from 0",
+            ));
+        }
+
+        Ok(Some(Self {
+            name: container.sort_key_name().unwrap_or_else(|| "sk".into()),
+            separator: container.sort_key_separator().unwrap_or_else(|| "#".into()),
+            fields: ordered.into_iter().map(|(_, _, field)| field).collect(),
+        }))
+    }
+
+    fn contains(
+        &self,
+        field: &ItemField,
+    ) -> bool {
+        self.fields
+            .iter()
+            .any(|f| std::ptr::eq(f.field, field.field))
+    }
+
+    /// Returns true if `field` is the first component in declaration order
+    fn is_first_declared(
+        &self,
+        field: &ItemField,
+        fields: &[ItemField],
+    ) -> bool {
+        fields
+            .iter()
+            .find(|f| self.contains(f))
+            .map(|f| std::ptr::eq(f.field, field.field))
+            .unwrap_or(false)
+    }
+
+    /// An `AttributeValue` expression joining each component where `access`
+    /// resolves an expression for a component's value
+    fn join(
+        &self,
+        access: impl Fn(&ItemField) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let separator = &self.separator;
+        let components = self.fields.iter().map(|field| {
+            let value = access(field);
+            quote!(::std::string::ToString::to_string(&#value))
+        });
+        quote!(::dynomite::composite::into_attr(&[#(#components),*], #separator))
+    }
+
+    /// A `field_name: value` initializer parsing a component from a mutable `attrs`
+    /// attribute map. The last component resolved removes the composite attribute
+    fn component(
+        &self,
+        field: &ItemField,
+        remove: bool,
+    ) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let separator = &self.separator;
+        let count = self.fields.len();
+        let index = self
+            .fields
+            .iter()
+            .position(|f| std::ptr::eq(f.field, field.field))
+            .expect("field should be a component");
+        let value = if remove {
+            quote!(&attrs.remove(#name))
+        } else {
+            quote!(attrs.get(#name))
+        };
+        let field_ident = &field.field.ident;
+        quote! {
+            #field_ident: ::dynomite::composite::from_attr(
+                #value.ok_or(::dynomite::AttributeError::MissingField { name: #name.to_string() })?,
+                #separator,
+                #count,
+                #index
+            )?
+        }
+    }
+}
+
+/// Extends a partition key count error with the likely intended partition key when
+/// none were declared, or the location of each when several were
+fn partition_key_count_error(
+    name: &Ident,
+    message: String,
+    fields: &[ItemField],
+) -> syn::Error {
+    let field_name = |field: &ItemField| {
+        field
+            .field
+            .ident
+            .as_ref()
+            .expect("should have an identifier")
+            .to_string()
+    };
+    let partition_keys = fields
+        .iter()
+        .filter(|field| field.is_partition_key())
+        .collect::<Vec<_>>();
+    if partition_keys.is_empty() {
+        let candidates = fields.iter().filter(|field| !field.is_skipped());
+        let candidate = candidates
+            .clone()
+            .find(|field| matches!(field_name(field).as_str(), "id" | "pk"))
+            .or_else(|| {
+                candidates.clone().find(|field| match &field.field.ty {
+                    Type::Path(path) => path.path.is_ident("String"),
+                    _ => false,
+                })
+            });
+        let message = match candidate {
+            Some(field) => format!(
+                "{}. Did you mean to mark `{}` with `#[dynomite(partition_key)]`?",
+                message,
+                field_name(field)
+            ),
+            None => message,
+        };
+        return syn::Error::new(name.span(), message);
+    }
+    let names = partition_keys
+        .iter()
+        .map(|field| format!("`{}`", field_name(field)))
+        .collect::<Vec<_>>();
+    let mut error = syn::Error::new(name.span(), format!("{}: {}", message, names.join(", ")));
+    for field in partition_keys {
+        let ident = field
+            .field
+            .ident
+            .as_ref()
+            .expect("should have an identifier");
+        error.combine(syn::Error::new(ident.span(), "partition_key declared here"));
+    }
+    error
+}
+
+/// Returns true if any identifier within the type is one of `names`
+fn type_mentions(
+    ty: &Type,
+    names: &[&str],
+) -> bool {
+    fn mentions(
+        tokens: proc_macro2::TokenStream,
+        names: &[&str],
+    ) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => names.iter().any(|name| ident == *name),
+            proc_macro2::TokenTree::Group(group) => mentions(group.stream(), names),
+            _ => false,
+        })
+    }
+    mentions(ty.to_token_stream(), names)
+}
+
+// ::dynomite::Attributes => my_platform::dynomite::Attributes
+fn replace_crate_path(
+    tokens: proc_macro2::TokenStream,
+    path: &syn::Path,
+) -> proc_macro2::TokenStream {
+    use proc_macro2::{Group, Spacing, TokenTree};
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut replaced = proc_macro2::TokenStream::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let is_crate_path = match &tokens[index..] {
+            [TokenTree::Punct(first), TokenTree::Punct(second), TokenTree::Ident(ident), ..] => {
+                first.as_char() == ':'
+                    && first.spacing() == Spacing::Joint
+                    && second.as_char() == ':'
+                    && ident == "dynomite"
+            }
+            _ => false,
+        };
+        // only leading `::` separators begin a crate path, `other::dynomite` is left as is
+        let is_leading = match index.checked_sub(1).map(|previous| &tokens[previous]) {
+            Some(TokenTree::Ident(_)) => false,
+            Some(TokenTree::Punct(punct)) => punct.as_char() != '>',
+            _ => true,
+        };
+        if is_crate_path && is_leading {
+            path.to_tokens(&mut replaced);
+            index += 3;
+            continue;
+        }
+        match &tokens[index] {
+            TokenTree::Group(group) => {
+                let mut replaced_group =
+                    Group::new(group.delimiter(), replace_crate_path(group.stream(), path));
+                replaced_group.set_span(group.span());
+                replaced.extend(Some(TokenTree::Group(replaced_group)));
+            }
+            token => replaced.extend(Some(token.clone())),
+        }
+        index += 1;
+    }
+    replaced
+}
+
+fn parse_attrs(all_attrs: &[Attribute]) -> Vec<Attr> {
+    all_attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("dynomite"))
+        .flat_map(|attr| {
+            attr.parse_args_with(Punctuated::<Attr, Token![,]>::parse_terminated)
+                .unwrap_or_abort()
+        })
+        .collect()
+}
+
+/// Derives `dynomite::Item` type for struts with named fields
+///
+/// Alongside `Item`, derived items provide
+///
+/// * `ATTRIBUTE_NAMES` - the names of the attributes their unflattened fields are stored as
+/// * `from_attrs_ref(&Attributes)` - converts borrowed attributes, cloning only those the fields need
+/// * `fields(&self)` - lists each attribute in field declaration order
+/// * `attribute_to_field(name)` - maps an attribute name back to the name of its field
+/// * `try_into_attributes(self)` - converts fields with `Attribute::try_into_attr`, failing for values DynamoDB would reject
+/// * `put_input(self, table)` and `get_input(&self, table)` - build rusoto inputs, with dynomite's default `inputs` feature
+/// * `key_string(&self)` - joins the key values into a single string such as `"people#123"`
+/// * `field_name_eq(value)` and, for `String` sort keys, `field_name_begins_with(prefix)` - build scan filter fragments
+/// * `schema()` - describes the key attributes of the item's table
+/// * `key_attribute_names()` - lists the attribute names of the partition key followed by the sort key
+/// * `not_exists_condition()` and `exists_condition()` - build condition expressions and their names on the partition key
+/// * `{Name}Key` - a struct of the item's key fields, whose `parse_key_string(&str)` inverts `key_string`
+/// * `{Name}Query` - a builder of `QueryInput`s, for non-generic items
+///
+/// The "Derived items in depth" section of dynomite's crate docs describes these in detail
+///
+/// # Attributes
+///
+/// * `#[dynomite(partition_key)]` - required attribute, marks the item's [partition attribute](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.PrimaryKey) field of a String, Number or Binary type
+/// * `#[dynomite(sort_key)]` - optional attribute, marks one [sort attribute](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.CoreComponents.html#HowItWorks.CoreComponents.SecondaryIndexes) field of a String, Number or Binary type
+/// * `#[dynomite(sort_key, order = N)]` - optional attribute, joins several fields in order into one composite sort key
+/// * `#[dynomite(key_as = "Type")]` - optional attribute, declares a key field with `Type` in the `{Name}Key` struct
+/// * `#[dynomite(key_rename = "keyName")]` - optional attribute, stores a key field under another name in `{Name}Key` and `Item::key`
+/// * `#[dynomite(rename = "actualName")]` - optional attribute, stores a field under a name which doesn't follow Rust's naming conventions
+/// * `#[dynomite(alias = "legacyName")]` - optional attribute, may be repeated to also read a field from other attribute names
+/// * `#[dynomite(default)]` - optional attribute, falls back to the field type's `Default` when the attribute is absent
+/// * `#[dynomite(default_value = 42)]` - optional attribute, falls back to an integer, float, bool or string literal instead
+/// * `#[dynomite(default_on_error)]` - optional attribute, also falls back to the `Default` when the attribute fails to convert
+/// * `#[dynomite(null_when_default)]` - optional attribute, writes a `NULL` in place of a field's `Default` value
+/// * `#[dynomite(read_only)]` - optional attribute, reads a field, or its `Default` when absent, but never writes it
+/// * `#[dynomite(skip_serializing)]` - optional attribute, reads a field as usual but never writes it
+/// * `#[dynomite(skip_deserializing)]` - optional attribute, writes a field but always reads its `Default`
+/// * `#[dynomite(encrypt_with = "path::to::module")]` - optional attribute, encrypts a non-key field with the module's `encrypt` and `decrypt` functions, omitting it from scan filters and `{Name}Update` structs
+/// * `#[dynomite(map)]` - optional attribute, stores a field as a nested `M` map, the default for derived types
+/// * `#[dynomite(flatten)]` - optional attribute, hoists a field's attributes into the item, optionally as `flatten(prefix = "prefix_")`
+/// * `#[dynomite(flatten, key)]` - optional attribute, takes the item's keys from a flattened field whose type derives `Item`
+/// * `#[dynomite(ttl)]` - optional attribute, stores a field as the epoch seconds DynamoDB's [time to live](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/TTL.html) expects
+/// * `#[dynomite(epoch)]` - optional attribute, stores a field as fractional epoch seconds
+/// * `#[dynomite(lenient_number)]` - optional attribute, also reads numbers stored as `S` strings
+/// * `#[dynomite(compress = "gzip")]` - optional attribute, gzip compresses an `S` or `B` field, with dynomite's `gzip` feature
+/// * `#[dynomite(json)]` - optional attribute, stores a serde field as a JSON string, with dynomite's `serde_json` feature
+/// * `#[dynomite(version)]` - optional attribute, marks one integer field for optimistic locking
+/// * `#[dynomite(created_at)]` - optional attribute, assigns the current time to an unset field when writing
+/// * `#[dynomite(updated_at)]` - optional attribute, assigns the current time to a field whenever writing
+/// * `#[dynomite(track_presence)]` - optional attribute, records whether a `default` or `Option` field's attribute was present
+/// * `#[dynomite(presence)]` - optional attribute, marks the `HashSet<&'static str>` field `track_presence` records into
+/// * `#[dynomite(rename_all = "camelCase")]` - optional container attribute, renames fields without a `rename` of their own
+/// * `#[dynomite(rename_all_serialize = "camelCase")]` and `#[dynomite(rename_all_deserialize = "snake_case")]` - optional container attributes, like `rename_all` for only writing or reading
+/// * `#[dynomite(sort_key_name = "sk")]` and `#[dynomite(sort_key_separator = "#")]` - optional container attributes, name and separate composite sort keys
+/// * `#[dynomite(key_delimiter = "/")]` - optional container attribute, joins `key_string` values, defaulting to `#`
+/// * `#[dynomite(table = "name")]` - optional container attribute, names the item's table as its `TABLE_NAME` const
+/// * `#[dynomite(crud)]` - optional container attribute, generates async `get`, `save` and `delete` methods, with dynomite's `crud` feature
+/// * `#[dynomite(crate = "path::to::dynomite")]` - optional container attribute, the path generated code refers to dynomite by
+/// * `#[dynomite(skip_key_struct)]` - optional container attribute, suppresses the `{Name}Key` struct
+/// * `#[dynomite(key_struct = "path::to::Key")]` - optional container attribute, reuses an existing struct as the key struct
+/// * `#[dynomite(key_hash)]` - optional container attribute, derives `Eq` and `Hash` for the `{Name}Key` struct
+/// * `#[dynomite(skip_query_struct)]` - optional container attribute, suppresses the `{Name}Query` builder
+/// * `#[dynomite(skip_scan_filters)]` - optional container attribute, suppresses the scan filter functions
+/// * `#[dynomite(update_struct)]` - optional container attribute, generates a `{Name}Update` struct of optional non-key fields
+/// * `#[dynomite(derive_default)]` - optional container attribute, generates a `Default` impl from each field's fallback value
+/// * `#[dynomite(ordered)]` - optional container attribute, generates an `into_ordered_attributes` method sorted by name
+/// * `#[dynomite(changed_fields)]` - optional container attribute, generates a `changed_fields(&self, other)` method listing differing attributes
+/// * `#[dynomite(merge)]` - optional container attribute, generates a `merge(&mut self, other)` method overlaying a partial item
+/// * `#[dynomite(deny_unknown_fields)]` - optional container attribute, fails reads which leave unknown attributes
+///
+/// Fields may be conditionally compiled with `#[cfg(...)]` and `PhantomData` fields are never stored
+///
+/// # Panics
+///
+/// This proc macro will panic when applied to other types
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(Item, attributes(partition_key, sort_key, dynomite))]
+pub fn derive_item(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input);
+
+    let gen = match expand_item(ast) {
+        Ok(g) => g,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    gen.into_token_stream().into()
+}
+
+/// similar in spirit to `#[derive(Item)]` except these are exempt from declaring
+/// partition and sort keys
+///
+/// This may also be applied to enums whose variants all have named fields. These are
+/// represented as a map with a single entry keyed by the variant name whose value is
+/// a map of the variant's fields
+///
+/// Enums marked with `#[dynomite(tag = "kind")]` instead store the variant name as an
+/// `S` attribute named `kind` alongside the variant's fields. Those also marked with
+/// `#[dynomite(content = "data")]` store the variant's fields as a map named `data`
+/// next to the tag
+///
+/// Newtype structs whose single field is marked `#[dynomite(flatten)]`, such as
+/// `struct Wrapper(#[dynomite(flatten)] Inner)`, are represented by the same attributes
+/// as the type they wrap
+///
+/// Structs marked with `#[dynomite(wrapped = "item")]` store their fields within a single
+/// `M` attribute named `item`, as some producers, such as event envelopes, do. Reading
+/// fails with `AttributeError::MissingField` when the wrapper is absent and with
+/// `AttributeError::InvalidType` when it is not a map. Items may not be wrapped as their
+/// keys must be top level attributes, nor may wrapped structs `deny_unknown_fields`
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(Attributes, attributes(dynomite))]
+pub fn derive_attributes(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input);
+
+    let gen = match expand_attributes(ast) {
+        Ok(g) => g,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    gen.into_token_stream().into()
+}
+
+/// Derives `dynomite::Attribute` for enum types
+///
+/// Enums marked with `#[dynomite(case_insensitive)]` also accept stored variant names
+/// which differ only in ASCII case, such as `"ACTIVE"` for `Active`. Variant names are
+/// always written as declared
+///
+/// Enums marked with `#[dynomite(display)]` also implement `Display`, writing the
+/// same name as is stored, and those marked with `#[dynomite(from_str)]` implement `FromStr`,
+/// parsing names exactly as `from_attr` does
+///
+/// Enums marked with `#[dynomite(as_str)]` also provide an `as_str(&self) -> &'static str`
+/// method and an `AsRef<str>` impl returning the name stored for each variant, including any
+/// prefix, without allocating. These may not capture unrecognized values with an `other` variant
+///
+/// Variants may be marked with `#[dynomite(rename = "...")]` to be stored under an explicit
+/// name rather than their own. This also serves stable short codes, much like string
+/// discriminants, such as `#[dynomite(rename = "A")] Active`, which keep stored values
+/// compact and independent of later variant renames. Stored names must be unique and
+/// unknown values fail to parse with `AttributeError::InvalidFormat`. Enums marked with `#[dynomite(prefix = "STATUS#")]` store each
+/// name after a shared prefix, such as `"STATUS#active"`, and fail to parse values missing it
+/// with `AttributeError::InvalidFormat` unless also marked `#[dynomite(lenient)]`
+///
+/// One variant may be marked with `#[dynomite(other)]` to resolve unrecognized values
+/// rather than failing with `AttributeError::InvalidFormat`. Unit variants act as a catch-all,
+/// while single `String` field tuple variants like `Unknown(String)` capture the
+/// raw value and write it back out verbatim. All other variants must be unit variants
+///
+/// Single field structs marked with `#[dynomite(transparent)]` derive an `Attribute`
+/// impl which delegates to that of their field. This is useful for newtypes
+///
+/// # Panics
+///
+/// This proc macro will panic when applied to other types
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(Attribute, attributes(dynomite))]
+pub fn derive_attribute(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input);
+
+    let gen = match expand_attribute(ast) {
+        Ok(g) => g,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    gen.into_token_stream().into()
+}
+
+fn expand_attribute(ast: DeriveInput) -> syn::Result<impl ToTokens> {
+    let name = &ast.ident;
+    let container = Container::new(&ast.attrs);
+    match ast.data {
+        Enum(variants) => make_dynomite_attr(
+            name,
+            &container,
+            &variants.variants.into_iter().collect::<Vec<_>>(),
+        )
+        .map(|tokens| container.with_crate_path(tokens)),
+        Struct(DataStruct { fields, .. }) if container.transparent() => {
+            make_dynomite_transparent_attr(name, &ast.generics, &fields)
+                .map(|tokens| container.with_crate_path(tokens))
+        }
+        _ => panic!(
+            "Dynomite Attributes can only be generated for enum types or transparent structs"
+        ),
+    }
+}
+
+/// ```rust,ignore
+/// impl<T> ::dynomite::Attribute for Name<T> where T: ::dynomite::Attribute {
+///   fn into_attr(self) -> ::dynomite::dynamodb::AttributeValue {
+///     ::dynomite::Attribute::into_attr(self.0)
+///   }
+///   fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
+///     ::dynomite::Attribute::from_attr(value).map(|inner| Name(inner))
+///   }
+/// }
+/// ```
+fn make_dynomite_transparent_attr(
+    name: &Ident,
+    generics: &Generics,
+    fields: &Fields,
+) -> syn::Result<impl ToTokens> {
+    let attr = quote!(::dynomite::Attribute);
+    let err = quote!(::dynomite::AttributeError);
+    if fields.len() != 1 {
+        return Err(syn::Error::new(
+            name.span(),
+            format!(
+                "Dynomite transparent Attributes must declare exactly one field. The `{}` struct declared {}",
+                name,
+                fields.len()
+            ),
+        ));
+    }
+    let (access, construct) = match fields {
+        Fields::Named(named) => {
+            let field = &named.named[0].ident;
+            (quote!(self.#field), quote!(#name { #field: inner }))
+        }
+        _ => (quote!(self.0), quote!(#name(inner))),
+    };
+    let inner_ty = &fields.iter().next().expect("checked to have one field").ty;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause
+        .into_iter()
+        .flat_map(|where_clause| where_clause.predicates.iter());
+    Ok(quote! {
+        impl #impl_generics #attr for #name #ty_generics
+        where
+            #(#predicates,)*
+            #inner_ty: #attr
+        {
+            fn into_attr(self: Self) -> ::dynomite::dynamodb::AttributeValue {
+                #attr::into_attr(#access)
+            }
+            fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> ::std::result::Result<Self, #err> {
+                #attr::from_attr(value).map(|inner| #construct)
+            }
+            fn try_into_attr(self: Self) -> ::std::result::Result<::dynomite::dynamodb::AttributeValue, #err> {
+                #attr::try_into_attr(#access)
+            }
+        }
+    })
+}
+
+/// ```rust,ignore
+/// impl ::dynomite::Attribute for Name {
+///   fn into_attr(self) -> ::dynomite::dynamodb::AttributeValue {
+///     let arm = match self {
+///        Name::Variant => "Variant".to_string()
+///     };
+///     ::dynomite::dynamodb::AttributeValue {
+///        s: Some(arm),
+///        ..Default::default()
+///     }
+///   }
+///   fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
+///     value.s.ok_or(::dynomite::AttributeError::InvalidType)
+///       .and_then(|value| match &value[..] {
+///          "Variant" => Ok(Name::Variant),
+///          _ => Err(::dynomite::AttributeError::InvalidFormat)
+///       })
+///   }
+/// }
+/// ```
+///
+/// `#[dynomite(case_insensitive)]` enums fall back to comparing
+/// each variant name ignoring ASCII case before failing, and enums
+/// with a `#[dynomite(other)]` variant resolve unrecognized values as it.
+/// A `#[dynomite(prefix)]` is prepended when writing and stripped before matching
+fn make_dynomite_attr(
+    name: &Ident,
+    container: &Container,
+    variants: &[Variant],
+) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let attr = quote!(::dynomite::Attribute);
+    let err = quote!(::dynomite::AttributeError);
+
+    let others = variants
+        .iter()
+        .filter_map(|var| {
+            parse_attrs(&var.attrs)
+                .into_iter()
+                .find_map(|attr| match attr {
+                    Attr::Other(ident) => Some((var, ident)),
+                    _ => None,
+                })
+        })
+        .collect::<Vec<_>>();
+    let other = match others.as_slice() {
+        [] => None,
+        [(var, attr)] => match &var.fields {
+            Fields::Unit => Some((*var, false)),
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                let field_ty = &unnamed.unnamed[0].ty;
+                let is_string = match field_ty {
+                    Type::Path(path) => path
+                        .path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident == "String")
+                        .unwrap_or(false),
+                    _ => false,
+                };
+                if !is_string {
+                    return Err(syn::Error::new(
+                        field_ty.span(),
+                        "other variants capturing the raw value must hold it in a single `String` field",
+                    ));
+                }
+                Some((*var, true))
+            }
+            _ => return Err(syn::Error::new(
+                attr.span(),
+                "other variants must be unit variants or capture the raw value in a single field",
+            )),
+        },
+        [_, (_, attr), ..] => {
+            return Err(syn::Error::new(
+                attr.span(),
+                format!(
+                    "Only one other variant may be declared. Found {}",
+                    others.len()
+                ),
+            ))
+        }
+    };
+    // a capturing variant has no name of its own to match
+    let is_capturing = |var: &Variant| {
+        other
+            .map(|(other, capturing)| capturing && std::ptr::eq(other, var))
+            .unwrap_or(false)
+    };
+    if let Some(var) = variants
+        .iter()
+        .find(|var| !is_capturing(var) && !matches!(var.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new(
+            var.fields.span(),
+            format!(
+                "Dynomite Attribute enums may only declare unit variants, besides an other variant capturing the raw value. The `{}` variant declares fields",
+                var.ident
+            ),
+        ));
+    }
+    let stored_name = |var: &Variant| {
+        parse_attrs(&var.attrs)
+            .into_iter()
+            .find_map(|attr| match attr {
+                Attr::Rename(_, lit) => Some(lit),
+                _ => None,
+            })
+            .unwrap_or_else(|| LitStr::new(&var.ident.to_string(), var.ident.span()))
+    };
+    let prefix = container.prefix();
+    if let (None, Some(lenient)) = (prefix, container.lenient()) {
+        return Err(syn::Error::new(
+            lenient.span(),
+            "lenient may only be used along with a prefix",
+        ));
+    }
+    // two variants stored under one name could never both be read back
+    let mut seen = std::collections::HashSet::new();
+    for var in variants.iter().filter(|var| !is_capturing(var)) {
+        let stored = stored_name(var);
+        if !seen.insert(stored.value()) {
+            return Err(syn::Error::new(
+                stored.span(),
+                format!(
+                    "variant name {:?} is declared more than once",
+                    stored.value()
+                ),
+            ));
+        }
+    }
+
+    // (pattern, String expression, &str expression) of each variant's stored name
+    let names = variants
+        .iter()
+        .map(|var| {
+            let vname = &var.ident;
+            if is_capturing(var) {
+                (
+                    quote!(#name::#vname(value)),
+                    quote!(value),
+                    quote!(value.as_str()),
+                )
+            } else {
+                let stored = stored_name(var);
+                (
+                    quote!(#name::#vname),
+                    quote!(#stored.to_string()),
+                    quote!(#stored),
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+    let into_match_arms = names.iter().map(|(pattern, value, _)| {
+        quote! {
+            #pattern => #value,
+        }
+    });
+    let into_value = match prefix {
+        Some(prefix) => quote!(::std::format!("{}{}", #prefix, arm)),
+        None => quote!(arm),
+    };
+    let display = if container.display() {
+        let display_match_arms = names.iter().map(|(pattern, _, value)| {
+            quote! {
+                #pattern => f.write_str(#value),
+            }
+        });
+        let write_prefix = prefix.map(|prefix| quote!(f.write_str(#prefix)?;));
+        Some(quote! {
+            impl ::std::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #write_prefix
+                    match self {
+                        #(#display_match_arms)*
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+    // stored names are literals, including their prefix, and so outlive any instance
+    let as_str = match (container.as_str(), other) {
+        (Some(as_str), Some((_, true))) => {
+            return Err(syn::Error::new(
+                as_str.span(),
+                "as_str may not be used along with an other variant capturing the raw value",
+            ))
+        }
+        (Some(_), _) => {
+            let as_str_match_arms = names.iter().map(|(pattern, _, value)| match prefix {
+                Some(prefix) => quote!(#pattern => ::std::concat!(#prefix, #value),),
+                None => quote!(#pattern => #value,),
+            });
+            Some(quote! {
+                impl #name {
+                    /// Returns the name this variant is stored as
+                    pub fn as_str(&self) -> &'static str {
+                        match self {
+                            #(#as_str_match_arms)*
+                        }
+                    }
+                }
+
+                impl ::std::convert::AsRef<str> for #name {
+                    fn as_ref(&self) -> &str {
+                        self.as_str()
+                    }
+                }
+            })
+        }
+        (None, _) => None,
+    };
+    let from_match_arms = variants.iter().filter(|var| !is_capturing(var)).map(|var| {
+        let vname = &var.ident;
+        let stored = stored_name(var);
+        quote! {
+            #stored => ::std::result::Result::Ok(#name::#vname),
+        }
+    });
+    // parsing delegates to from_attr so that both share one mapping
+    let from_str = if container.from_str() {
+        Some(quote! {
+            impl ::std::str::FromStr for #name {
+                type Err = #err;
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    #attr::from_attr(::dynomite::dynamodb::AttributeValue {
+                        s: ::std::option::Option::Some(s.to_string()),
+                        ..::std::default::Default::default()
+                    })
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let fallback_checks = variants
+        .iter()
+        .filter(|var| container.case_insensitive() && !is_capturing(var))
+        .map(|var| {
+            let vname = &var.ident;
+            let stored = stored_name(var);
+            quote! {
+                if value.eq_ignore_ascii_case(#stored) {
+                    return ::std::result::Result::Ok(#name::#vname);
+                }
+            }
+        });
+    let fallback = match other {
+        Some((var, true)) => {
+            let vname = &var.ident;
+            quote!(::std::result::Result::Ok(#name::#vname(value.to_string())))
+        }
+        Some((var, false)) => {
+            let vname = &var.ident;
+            quote!(::std::result::Result::Ok(#name::#vname))
+        }
+        None => quote!(::std::result::Result::Err(
+            ::dynomite::AttributeError::InvalidFormat
+        )),
+    };
+    let fallback_arm = if container.case_insensitive() || matches!(other, Some((_, true))) {
+        quote! {
+            value => {
+                #(#fallback_checks)*
+                #fallback
+            }
+        }
+    } else {
+        quote!(_ => #fallback)
+    };
+    // unprefixed values are only accepted by lenient enums
+    let unprefixed = if container.lenient().is_some() {
+        quote!(&value[..])
+    } else {
+        quote!(return ::std::result::Result::Err(::dynomite::AttributeError::InvalidFormat))
+    };
+    let stripped = match prefix {
+        Some(prefix) => quote! {
+            let value = match value.strip_prefix(#prefix) {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => #unprefixed,
+            };
+        },
+        None => quote!(let value = &value[..];),
+    };
+
+    Ok(quote! {
+        impl #attr for #name {
+            fn into_attr(self) -> ::dynomite::dynamodb::AttributeValue {
+                let arm = match self {
+                    #(#into_match_arms)*
+                };
+                ::dynomite::dynamodb::AttributeValue {
+                    s: ::std::option::Option::Some(#into_value),
+                    ..::std::default::Default::default()
+                }
+            }
+            fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> ::std::result::Result<Self, #err> {
+                value.s.ok_or(::dynomite::AttributeError::InvalidType)
+                    .and_then(|value| {
+                        #stripped
+                        match value {
+                            #(#from_match_arms)*
+                            #fallback_arm
+                        }
+                    })
+            }
+        }
+
+        #display
+        #from_str
+        #as_str
+    })
+}
+
+fn expand_attributes(ast: DeriveInput) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let name = &ast.ident;
+    let container = Container::new(&ast.attrs);
+    match ast.data {
+        Struct(DataStruct { fields, .. }) => match fields {
+            Fields::Named(named) => make_dynomite_attributes(
+                name,
+                &ast.generics,
+                &container,
+                &named.named.into_iter().collect::<Vec<_>>(),
+            )
+            .map(|tokens| container.with_crate_path(tokens)),
+            Fields::Unnamed(unnamed) => {
+                make_dynomite_attributes_newtype(name, &ast.generics, &unnamed)
+                    .map(|tokens| container.with_crate_path(tokens))
+            }
+            fields => Err(syn::Error::new(
+                fields.span(),
+                "Dynomite Attributes require named fields",
+            )),
+        },
+        Enum(DataEnum { variants, .. }) => make_dynomite_attributes_enum(
+            name,
+            &container,
+            &variants.into_iter().collect::<Vec<_>>(),
+        )
+        .map(|tokens| container.with_crate_path(tokens)),
+        _ => panic!("Dynomite Attributes can only be generated for structs and enums"),
+    }
+}
+
+fn expand_item(ast: DeriveInput) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let container = Container::new(&ast.attrs);
+    match ast.data {
+        Struct(DataStruct { fields, .. }) => match fields {
+            Fields::Named(named) => make_dynomite_item(
+                vis,
+                name,
+                &ast.generics,
+                &container,
+                &named.named.into_iter().collect::<Vec<_>>(),
+            )
+            .map(|tokens| container.with_crate_path(tokens)),
+            fields => Err(syn::Error::new(
+                fields.span(),
+                "Dynomite Items require named fields",
+            )),
+        },
+        _ => panic!("Dynomite Items can only be generated for structs"),
+    }
+}
+
+fn make_dynomite_attributes(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[Field],
+) -> syn::Result<impl ToTokens> {
+    let item_fields = item_fields(container, fields);
+    // impl ::dynomite::FromAttributes for Name
+    let from_attribute_map = get_from_attributes_trait(name, generics, container, &item_fields)?;
+    // impl From<Name> for ::dynomite::Attributes
+    let to_attribute_map = get_to_attribute_map_trait(name, generics, container, &item_fields)?;
+    // impl Name { ... }
+    let inherent_impl = get_inherent_impl(name, generics, container, &item_fields)?;
+    // impl Attribute for Name (these are essentially just a map)
+    let impl_attribute = get_attribute_trait(name, generics);
+    // impl Default for Name
+    let default = get_default_trait(name, generics, container, &item_fields);
+    // const _: fn() = || { assert_attribute::<FieldType>(); };
+    let assertions = get_attribute_assertions(generics, container, &item_fields)?;
+
+    Ok(quote! {
+        #from_attribute_map
+        #to_attribute_map
+        #impl_attribute
+        #inherent_impl
+        #default
+        #assertions
+    })
+}
+
+/// ```rust,ignore
+/// impl ::dynomite::FromAttributes for Name {
+///   fn from_attrs(attrs: ::dynomite::Attributes) -> Result<Self, ::dynomite::AttributeError> {
+///     ::dynomite::FromAttributes::from_attrs(attrs).map(Name)
+///   }
+/// }
+///
+/// impl From<Name> for ::dynomite::Attributes {
+///   fn from(value: Name) -> Self {
+///     value.0.into()
+///   }
+/// }
+/// ```
+fn make_dynomite_attributes_newtype(
+    name: &Ident,
+    generics: &Generics,
+    fields: &syn::FieldsUnnamed,
+) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let is_flatten_newtype = match fields.unnamed.iter().collect::<Vec<_>>().as_slice() {
+        [field] => {
+            let field = ItemField::new(field);
+            field.is_flatten() && field.flatten_prefix().is_none()
+        }
+        _ => false,
+    };
+    if !is_flatten_newtype {
+        return Err(syn::Error::new(
+            fields.span(),
+            "Dynomite Attributes require named fields, or a single unnamed `#[dynomite(flatten)]` field",
+        ));
+    }
+    let attributes = quote!(::dynomite::Attributes);
+    let err = quote!(::dynomite::AttributeError);
+    // TryFrom<Attributes> for Name
+    let try_from = get_try_from_attributes_trait(name, generics);
+    // impl Attribute for Name (the same map as the wrapped type)
+    let impl_attribute = get_attribute_trait(name, generics);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::dynomite::FromAttributes for #name #ty_generics #where_clause {
+            fn from_attrs(attrs: #attributes) -> ::std::result::Result<Self, #err> {
+                ::dynomite::FromAttributes::from_attrs(attrs).map(#name)
+            }
+
+            fn from_attrs_mut(attrs: &mut #attributes) -> ::std::result::Result<Self, #err> {
+                ::dynomite::FromAttributes::from_attrs_mut(attrs).map(#name)
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#name #ty_generics> for #attributes #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                value.0.into()
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the attributes of the wrapped value, failing rather than producing
+            /// values DynamoDB would reject, such as non-finite floats
+            pub fn try_into_attributes(self) -> ::std::result::Result<#attributes, #err> {
+                self.0.try_into_attributes()
+            }
+        }
+
+        #try_from
+        #impl_attribute
+    })
+}
+
+/// ```rust,ignore
+/// impl ::dynomite::Attribute for Name {
+///   fn into_attr(self: Self) -> ::dynomite::AttributeValue {
+///     ::dynomite::AttributeValue {
+///       m: Some(self.into()),
+///       ..::dynomite::AttributeValue::default()
+///     }
+///   }
+///   fn from_attr(value: ::dynomite::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
+///     value.m.ok_or(::dynomite::AttributeError::InvalidType).and_then(Self::from_attrs)
+///   }
+/// }
+/// ```
+///
+/// `try_into_attr` converts through the type's `try_into_attributes` function
+fn get_attribute_trait(
+    name: &Ident,
+    generics: &Generics,
+) -> impl ToTokens {
+    let attribute = quote!(::dynomite::Attribute);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #attribute for #name #ty_generics #where_clause {
+            fn into_attr(self: Self) -> ::dynomite::AttributeValue {
+                ::dynomite::AttributeValue {
+                    m: Some(self.into()),
+                    ..::dynomite::AttributeValue::default()
+                }
+            }
+            fn from_attr(value: ::dynomite::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
+                use ::dynomite::FromAttributes;
+                value
+                    .m
+                    .ok_or(::dynomite::AttributeError::InvalidType)
+                    .and_then(Self::from_attrs)
+            }
+            fn try_into_attr(self: Self) -> Result<::dynomite::AttributeValue, ::dynomite::AttributeError> {
+                ::std::result::Result::Ok(::dynomite::AttributeValue {
+                    m: Some(self.try_into_attributes()?),
+                    ..::dynomite::AttributeValue::default()
+                })
+            }
+        }
+    }
+}
+
+/// Enums are represented as a map with a single entry keyed by the variant name
+/// whose value is a map of the variant's fields
+///
+/// ```rust,ignore
+/// impl ::dynomite::FromAttributes for Name {
+///   fn from_attrs(attrs: ::dynomite::Attributes) -> Result<Self, ::dynomite::AttributeError> {
+///     if attrs.len() != 1 {
+///       return Err(::dynomite::AttributeError::InvalidFormat);
+///     }
+///     let (variant, value) = attrs.into_iter().next().ok_or(::dynomite::AttributeError::InvalidFormat)?;
+///     let fields = value.m.ok_or(::dynomite::AttributeError::InvalidType)?;
+///     match &variant[..] {
+///       "Variant" => {
+///         let mut attrs = fields;
+///         Ok(Name::Variant { field_name: ... })
+///       }
+///       _ => Err(::dynomite::AttributeError::InvalidFormat)
+///     }
+///   }
+/// }
+/// ```
+fn make_dynomite_attributes_enum(
+    name: &Ident,
+    enum_container: &Container,
+    variants: &[Variant],
+) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let attributes = quote!(::dynomite::Attributes);
+    let err = quote!(::dynomite::AttributeError);
+    let from = quote!(::std::convert::From);
+    let from_attrs = quote!(::dynomite::FromAttributes);
+    // variants have no container attributes of their own
+    let container = Container::new(&[]);
+    let (tag, content) = (enum_container.tag(), enum_container.content());
+    if let (None, Some(content)) = (tag, content) {
+        return Err(syn::Error::new(
+            content.span(),
+            "content may only be used along with a tag",
+        ));
+    }
+
+    let variant_fields = variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            Fields::Named(named) => Ok((
+                &variant.ident,
+                named.named.iter().map(ItemField::new).collect::<Vec<_>>(),
+            )),
+            _ => Err(syn::Error::new(
+                variant.ident.span(),
+                "Dynomite Attributes enums require variants with named fields",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // internally tagged fields share a map with the tag
+    if let (Some(tag), None) = (tag, content) {
+        if let Some(field) = variant_fields
+            .iter()
+            .flat_map(|(_, fields)| fields)
+            .find(|field| field.deser_name() == tag.value())
+        {
+            return Err(syn::Error::new(
+                field.field.span(),
+                format!("field attribute name conflicts with tag {:?}", tag.value()),
+            ));
+        }
+    }
+
+    let from_match_arms = variant_fields
+        .iter()
+        .map(|(vname, fields)| {
+            if fields.is_empty() {
+                return Ok(quote! {
+                    stringify!(#vname) => ::std::result::Result::Ok(#name::#vname {}),
+                });
+            }
+            let field_conversions = get_from_attributes_conversions(&container, fields)?;
+            let presence = get_presence_declaration(fields)?;
+            Ok(quote! {
+                stringify!(#vname) => {
+                    let mut fields = fields;
+                    let attrs = &mut fields;
+                    #presence
+                    ::std::result::Result::Ok(#name::#vname {
+                        #(#field_conversions),*
+                    })
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // fallible arms convert values with `try_into_attr`, returning early on errors
+    let into_match_arms = |fallible: bool| {
+        variant_fields
+            .iter()
+            .map(|(vname, fields)| {
+                // bind each field to a local we know won't collide with generated locals
+                let bindings = (0..fields.len())
+                    .map(|index| Ident::new(&format!("field_{}", index), Span::call_site()))
+                    .collect::<Vec<_>>();
+                let field_patterns = fields.iter().zip(&bindings).map(|(field, binding)| {
+                    let cfgs = field.cfg_attrs();
+                    let field_ident = &field.field.ident;
+                    // read only fields are never written
+                    match field.never_written() {
+                        Some(_) => quote!(#(#cfgs)* #field_ident: _),
+                        None => quote!(#(#cfgs)* #field_ident: #binding),
+                    }
+                });
+                let field_conversions =
+                    get_attribute_inserts(&container, fields, Sink::Map, fallible, |field| {
+                        let index = fields
+                            .iter()
+                            .position(|f| std::ptr::eq(f.field, field.field))
+                            .expect("field should belong to variant");
+                        bindings[index].to_token_stream()
+                    })?;
+                let values = if fields.is_empty() {
+                    quote!(let values = #attributes::new();)
+                } else {
+                    quote!(let mut values = #attributes::new();)
+                };
+                Ok(quote! {
+                    #name::#vname { #(#field_patterns),* } => {
+                        #values
+                        #(#field_conversions)*
+                        (stringify!(#vname), values)
+                    }
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()
+    };
+    let try_into_match_arms = into_match_arms(true)?;
+    let into_match_arms = into_match_arms(false)?;
+
+    let impl_attribute = get_attribute_trait(name, &Generics::default());
+    let try_from = get_try_from_attributes_trait(name, &Generics::default());
+
+    let (read_variant, write_variant) = match (tag, content) {
+        (Some(tag), content) => {
+            let fields = match content {
+                Some(content) => quote! {
+                    let fields = attrs
+                        .remove(#content)
+                        .ok_or_else(|| #err::MissingField {
+                            name: #content.to_string(),
+                        })?
+                        .m
+                        .ok_or(#err::InvalidType)?;
+                },
+                None => quote!(let fields = attrs;),
+            };
+            let attrs = match content {
+                Some(content) => quote! {
+                    let mut attrs = #attributes::new();
+                    attrs.insert(
+                        #content.to_string(),
+                        ::dynomite::AttributeValue {
+                            m: ::std::option::Option::Some(values),
+                            ..::std::default::Default::default()
+                        }
+                    );
+                },
+                None => quote!(let mut attrs = values;),
+            };
+            (
+                quote! {
+                    let mut attrs = attrs;
+                    let variant = attrs
+                        .remove(#tag)
+                        .ok_or_else(|| #err::MissingField {
+                            name: #tag.to_string(),
+                        })?
+                        .s
+                        .ok_or(#err::InvalidType)?;
+                    #fields
+                },
+                quote! {
+                    #attrs
+                    attrs.insert(
+                        #tag.to_string(),
+                        ::dynomite::AttributeValue {
+                            s: ::std::option::Option::Some(variant.to_string()),
+                            ..::std::default::Default::default()
+                        }
+                    );
+                    attrs
+                },
+            )
+        }
+        (None, _) => (
+            quote! {
+                if attrs.len() != 1 {
+                    return ::std::result::Result::Err(#err::InvalidFormat);
+                }
+                let (variant, value) = attrs
+                    .into_iter()
+                    .next()
+                    .ok_or(#err::InvalidFormat)?;
+                let fields = value.m.ok_or(#err::InvalidType)?;
+            },
+            quote! {
+                let mut attrs = #attributes::new();
+                attrs.insert(
+                    variant.to_string(),
+                    ::dynomite::AttributeValue {
+                        m: ::std::option::Option::Some(values),
+                        ..::std::default::Default::default()
+                    }
+                );
+                attrs
+            },
+        ),
+    };
+
+    Ok(quote! {
+        impl #from_attrs for #name {
+            fn from_attrs(attrs: #attributes) -> ::std::result::Result<Self, #err> {
+                #read_variant
+                match &variant[..] {
+                    #(#from_match_arms)*
+                    _ => ::std::result::Result::Err(#err::InvalidFormat)
+                }
+            }
+        }
+
+        impl #from<#name> for #attributes {
+            fn from(item: #name) -> Self {
+                let (variant, values) = match item {
+                    #(#into_match_arms)*
+                };
+                #write_variant
+            }
+        }
+
+        impl #name {
+            /// Returns the attributes of this value, as `Into<Attributes>` does, failing
+            /// rather than producing values DynamoDB would reject, such as non-finite floats
+            pub fn try_into_attributes(self) -> ::std::result::Result<#attributes, #err> {
+                let (variant, values) = match self {
+                    #(#try_into_match_arms)*
+                };
+                ::std::result::Result::Ok({
+                    #write_variant
+                })
+            }
+        }
+
+        #impl_attribute
+        #try_from
+    })
+}
+
+fn make_dynomite_item(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[Field],
+) -> syn::Result<impl ToTokens> {
+    if let Some((wrapped, _)) = container.wrapped() {
+        return Err(syn::Error::new(
+            wrapped.span(),
+            "wrapped may only be applied to Attributes. Item keys must be top level attributes",
+        ));
+    }
+    let item_fields = item_fields(container, fields);
+    let key_delegates = item_fields
+        .iter()
+        .filter_map(|field| field.key_delegate().map(|ident| (field, ident)))
+        .collect::<Vec<_>>();
+    for (field, ident) in &key_delegates {
+        if !field.is_flatten() || field.flatten_prefix().is_some() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "key may only be applied to flatten fields without a prefix",
+            ));
+        }
+    }
+    if let [_, (_, second), ..] = key_delegates.as_slice() {
+        return Err(syn::Error::new(
+            second.span(),
+            "only one flatten field may provide an Item's key",
+        ));
+    }
+    // all items must have 1 primary_key, either their own or their key delegate's
+    let partition_key_count = item_fields.iter().filter(|f| f.is_partition_key()).count();
+    if let Some((_, ident)) = key_delegates.first() {
+        if partition_key_count > 0 || item_fields.iter().any(|f| f.is_sort_key()) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "key flatten fields provide an Item's partition_key and sort_key, which may not also be declared by the Item's own fields",
+            ));
+        }
+    } else if partition_key_count != 1 {
+        let message = format!(
+            "All Item's must declare one and only one partition_key. The `{}` Item declared {}",
+            name, partition_key_count
+        );
+        return Err(partition_key_count_error(name, message, &item_fields));
+    }
+    if let Some(sort_key) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key())
+        .find_map(|field| {
+            field.attrs.iter().find_map(|attr| match attr {
+                Attr::SortKey(ident) => Some(ident),
+                _ => None,
+            })
+        })
+    {
+        return Err(syn::Error::new(
+            sort_key.span(),
+            "A field may not be both a partition_key and a sort_key. Partition and sort keys must be distinct attributes",
+        ));
+    }
+    if let Some(skip) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .find_map(|field| field.never_written().or_else(|| field.skip_deserializing()))
+    {
+        return Err(syn::Error::new(
+            skip.span(),
+            format!(
+                "{} may not be applied to a partition_key or sort_key. Key attributes are always written and read",
+                skip
+            ),
+        ));
+    }
+    if let Some((encrypt_with, _)) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .find_map(|field| field.encrypt_with())
+    {
+        return Err(syn::Error::new(
+            encrypt_with.span(),
+            "encrypt_with may not be applied to a partition_key or sort_key. Key attributes must remain readable by DynamoDB",
+        ));
+    }
+    // `Item::key` and key structs encode keys with their type's `Attribute` impl alone
+    if let Some(encoding) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .find_map(|field| {
+            field
+                .ttl()
+                .or_else(|| field.epoch())
+                .or_else(|| field.lenient_number())
+                .or_else(|| field.json())
+                .or_else(|| field.compress())
+                .or_else(|| field.map())
+        })
+    {
+        return Err(syn::Error::new(
+            encoding.span(),
+            format!(
+                "{} may not be applied to a partition_key or sort_key. Key attributes are encoded by their type's `Attribute` impl alone",
+                encoding
+            ),
+        ));
+    }
+    if let Some(null_when_default) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .find_map(|field| field.null_when_default())
+    {
+        return Err(syn::Error::new(
+            null_when_default.span(),
+            "null_when_default may not be applied to a partition_key or sort_key. Key attributes may not be NULL",
+        ));
+    }
+    if let Some((key_rename, _)) = item_fields
+        .iter()
+        .filter(|field| {
+            !(field.is_partition_key() || field.is_sort_key()) || field.order().is_some()
+        })
+        .find_map(|field| field.key_rename())
+    {
+        return Err(syn::Error::new(
+            key_rename.span(),
+            "key_rename may only be applied to a partition_key or a sort_key which is not a composite sort key component",
+        ));
+    }
+    // best effort, custom scalar types can not be told apart from structs
+    if let Some(field) = item_fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .find(|field| is_non_scalar(&field.field.ty))
+    {
+        return Err(syn::Error::new_spanned(
+            &field.field.ty,
+            "Key fields must be stored as scalar S, N or B attributes. Tuples, arrays other than `[u8; N]` and collections other than `Vec<u8>` may not be used as a partition_key or sort_key",
+        ));
+    }
+    // impl Item for Name + NameKey struct
+    let dynamodb_traits = get_dynomite_item_traits(vis, name, generics, container, &item_fields)?;
+    // impl ::dynomite::FromAttributes for Name
+    let from_attribute_map = get_from_attributes_trait(name, generics, container, &item_fields)?;
+    // impl From<Name> for ::dynomite::Attributes
+    let to_attribute_map = get_to_attribute_map_trait(name, generics, container, &item_fields)?;
+    // impl Name { ... }
+    let inherent_impl = get_inherent_impl(name, generics, container, &item_fields)?;
+    // impl Default for Name
+    let default = get_default_trait(name, generics, container, &item_fields);
+    // impl Attribute for Name (items may be nested as maps)
+    let impl_attribute = get_attribute_trait(name, generics);
+    // const _: fn() = || { assert_attribute::<FieldType>(); };
+    let assertions = get_attribute_assertions(generics, container, &item_fields)?;
+
+    Ok(quote! {
+        #from_attribute_map
+        #to_attribute_map
+        #dynamodb_traits
+        #inherent_impl
+        #default
+        #impl_attribute
+        #assertions
+    })
+}
+
+/// ```rust,ignore
+/// impl Default for Name {
+///   fn default() -> Self {
+///     Self {
+///       field_name: <FieldType as Default>::default(),
+///     }
+///   }
+/// }
+/// ```
+fn get_default_trait(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> Option<impl ToTokens> {
+    if !container.derive_default() {
+        return None;
+    }
+    let field_defaults = fields.iter().map(|field| {
+        let cfgs = field.cfg_attrs();
+        let field_ident = &field.field.ident;
+        let field_default = get_field_default(field);
+        quote!(#(#cfgs)* #field_ident: #field_default)
+    });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_defaults),*
+                }
+            }
+        }
+    })
+}
+
+// the fallback value of a field absent in DynamoDB, its `default_value` literal if any,
+// spanned such that literals of another type are reported at the literal
+//
+// <FieldType as From<&'static str>>::from("value")
+fn get_field_default(field: &ItemField) -> proc_macro2::TokenStream {
+    match field.default_value() {
+        Some(Lit::Str(lit)) => {
+            let ty = &field.field.ty;
+            quote_spanned!(lit.span()=> <#ty as ::std::convert::From<&'static str>>::from(#lit))
+        }
+        Some(lit) => quote_spanned!(lit.span()=> #lit),
+        None => get_default_value(&field.field.ty).into_token_stream(),
+    }
+}
+
+// the fallback value of fields absent in DynamoDB, spanned such that
+// types lacking a Default impl are reported at the field's type
+//
+// <FieldType as Default>::default()
+fn get_default_value(ty: &Type) -> impl ToTokens {
+    use syn::spanned::Spanned as _;
+    quote_spanned!(ty.span()=> <#ty as ::std::default::Default>::default())
+}
+
+// impl Name {
+//    pub fn changed_fields(&self, other: &Self) -> Vec<&'static str> {
+//      ...
+//    }
+//    pub fn merge(&mut self, other: Self) {
+//      ...
+//    }
+// }
+//
+fn get_inherent_impl(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let composite = CompositeSortKey::new(container, fields)?;
+    let changed_fields = container
+        .changed_fields()
+        .map(|attr| get_changed_fields_function(attr, composite.as_ref(), fields))
+        .transpose()?;
+    let merge = if container.merge() {
+        Some(get_merge_function(fields))
+    } else {
+        None
+    };
+    let version = get_version_functions(fields)?;
+    let attribute_to_field = get_attribute_to_field_function(composite.as_ref(), fields);
+    let attribute_names = get_attribute_names_const(composite.as_ref(), fields);
+    let from_attrs_ref = get_from_attrs_ref_function(container, fields);
+    let fields_function = get_fields_function(container, fields)?;
+    let try_into_attributes = get_try_into_attributes_function(container, fields)?;
+    let ordered = if container.ordered() {
+        Some(get_into_ordered_attributes_function())
+    } else {
+        None
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #attribute_names
+            #from_attrs_ref
+            #fields_function
+            #try_into_attributes
+            #attribute_to_field
+            #changed_fields
+            #merge
+            #version
+            #ordered
+        }
+    })
+}
+
+// asserts that each field stored through its type's `Attribute` impl has one, such that
+// a missing impl is reported at the field's type rather than within generated code.
+// Generic types are left to the generated impls as their parameters are out of scope here
+//
+// const _: fn() = || {
+//   fn assert_attribute<T: ::dynomite::Attribute>() {}
+//   assert_attribute::<FieldType>();
+// };
+fn get_attribute_assertions(
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<Option<impl ToTokens>> {
+    use syn::spanned::Spanned as _;
+    if !generics.params.is_empty() {
+        return Ok(None);
+    }
+    let composite = CompositeSortKey::new(container, fields)?;
+    let assertions = fields
+        .iter()
+        .filter(|field| {
+            !(field.is_skipped()
+                || field.is_flatten()
+                || field.map().is_some()
+                || field.ttl().is_some()
+                || field.epoch().is_some()
+                || field.is_json()
+                || composite
+                    .as_ref()
+                    .map(|composite| composite.contains(field))
+                    .unwrap_or(false))
+        })
+        .map(|field| {
+            let cfgs = field.cfg_attrs();
+            let field_ty = &field.field.ty;
+            quote_spanned! {field_ty.span()=>
+                #(#cfgs)*
+                assert_attribute::<#field_ty>();
+            }
+        })
+        .collect::<Vec<_>>();
+    if assertions.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(quote! {
+        const _: fn() = || {
+            fn assert_attribute<T: ::dynomite::Attribute>() {}
+            #(#assertions)*
+        };
+    }))
+}
+
+// pub fn try_into_attributes(self) -> Result<::dynomite::Attributes, ::dynomite::AttributeError> {
+//   let mut values = ::dynomite::Attributes::new();
+//   values.insert("field_deser_name".to_string(), ::dynomite::Attribute::try_into_attr(self.field)?);
+//   Ok(values)
+// }
+fn get_try_into_attributes_function(
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let field_conversions = get_attribute_inserts(container, fields, Sink::Map, true, |field| {
+        let field_ident = &field.field.ident;
+        quote!(self.#field_ident)
+    })?;
+    let wrap = get_wrap_statement(container);
+    Ok(quote! {
+        /// Returns the attributes of this value, as `Into<Attributes>` does, failing
+        /// rather than producing values DynamoDB would reject, such as non-finite floats
+        pub fn try_into_attributes(
+            self
+        ) -> ::std::result::Result<::dynomite::Attributes, ::dynomite::AttributeError> {
+            let mut values = ::dynomite::Attributes::new();
+            #(#field_conversions)*
+            #wrap
+            ::std::result::Result::Ok(values)
+        }
+    })
+}
+
+// pub fn fields(&self) -> Vec<(Cow<'static, str>, ::dynomite::AttributeValue)> {
+//   let mut values = Vec::new();
+//   values.push((Cow::Borrowed("field_deser_name"), ::dynomite::Attribute::into_attr(self.field.clone())));
+//   values
+// }
+fn get_fields_function(
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let field_conversions =
+        get_attribute_inserts(container, fields, Sink::Fields, false, |field| {
+            let field_ident = &field.field.ident;
+            quote!(::std::clone::Clone::clone(&self.#field_ident))
+        })?;
+    // as with `From<&Name> for Attributes`, higher-ranked bounds defer checking
+    // until the method is used
+    let clone_bounds = fields
+        .iter()
+        .filter(|field| !field.is_skipped())
+        .map(|field| {
+            let field_ty = &field.field.ty;
+            quote!(for<'__dynomite> #field_ty: ::std::clone::Clone)
+        });
+
+    Ok(quote! {
+        /// Returns each of this type's attributes in field declaration order,
+        /// without collecting them into an `Attributes` map. Flattened fields
+        /// contribute the attributes of their type in no particular order
+        #[allow(unused_mut)]
+        pub fn fields(
+            &self
+        ) -> ::std::vec::Vec<(::std::borrow::Cow<'static, str>, ::dynomite::AttributeValue)>
+        where
+            #(#clone_bounds),*
+        {
+            let mut values = ::std::vec::Vec::new();
+            #(#field_conversions)*
+            values
+        }
+    })
+}
+
+// pub fn into_ordered_attributes(self) -> BTreeMap<String, ::dynomite::AttributeValue> {
+//   ::dynomite::Attributes::from(self).into_iter().collect()
+// }
+fn get_into_ordered_attributes_function() -> impl ToTokens {
+    quote! {
+        /// Returns this type's attributes ordered by name
+        pub fn into_ordered_attributes(
+            self
+        ) -> ::std::collections::BTreeMap<::std::string::String, ::dynomite::AttributeValue> {
+            ::dynomite::Attributes::from(self).into_iter().collect()
+        }
+    }
+}
+
+// pub fn attribute_to_field(name: &str) -> Option<&'static str> {
+//   match name {
+//     "field_deser_name" => Some("field_name"),
+//     name if name.starts_with("flatten_prefix") => Some("flattened_field_name"),
+//     _ => None
+//   }
+// }
+fn get_attribute_to_field_function(
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    use syn::ext::IdentExt as _;
+    let arms = fields.iter().filter_map(|field| {
+        if field.is_skipped() {
+            return None;
+        }
+        let field_name = field
+            .field
+            .ident
+            .as_ref()
+            .expect("should have an identifier")
+            .unraw()
+            .to_string();
+        if field.is_flatten() {
+            return field.flatten_prefix().map(|prefix| {
+                quote! {
+                    name if name.starts_with(#prefix) => ::std::option::Option::Some(#field_name),
+                }
+            });
+        }
+        let field_deser_name = match composite.filter(|c| c.contains(field)) {
+            // the composite attribute resolves into each of its components,
+            // reported as the first of them
+            Some(composite) if std::ptr::eq(composite.fields[0].field, field.field) => {
+                composite.name.clone()
+            }
+            Some(_) => return None,
+            None => field.deser_name(),
+        };
+        let cfgs = field.cfg_attrs();
+        Some(quote! {
+            #(#cfgs)*
+            #field_deser_name => ::std::option::Option::Some(#field_name),
+        })
+    });
+
+    quote! {
+        /// Returns the name of the field which the named attribute is resolved into, if any.
+        /// Attributes of flattened fields without a prefix are not known
+        pub fn attribute_to_field(name: &str) -> ::std::option::Option<&'static str> {
+            match name {
+                #(#arms)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    }
+}
+
+// pub const ATTRIBUTE_NAMES: &[&str] = &["field_deser_name", ...];
+fn get_attribute_names_const(
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let names = fields
+        .iter()
+        .filter(|field| !(field.is_skipped() || field.is_flatten()))
+        .filter_map(|field| match composite.filter(|c| c.contains(field)) {
+            Some(composite) if composite.is_first_declared(field, fields) => {
+                let name = &composite.name;
+                Some(quote!(#name))
+            }
+            Some(_) => None,
+            None => {
+                let cfgs = field.cfg_attrs();
+                let name = field.deser_name();
+                Some(quote!(#(#cfgs)* #name))
+            }
+        });
+
+    quote! {
+        /// The names of the attributes this type's fields are stored as, in declaration order.
+        /// Attributes of flattened fields are not included
+        pub const ATTRIBUTE_NAMES: &[&str] = &[#(#names),*];
+    }
+}
+
+// pub fn from_attrs_ref(attrs: &::dynomite::Attributes) -> Result<Self, ::dynomite::AttributeError> {
+//   let mut needed = ::dynomite::Attributes::new();
+//   for name in Self::ATTRIBUTE_NAMES { .. }
+//   for (name, value) in attrs.iter().filter(|(name, _)| name.starts_with("flatten_prefix")) { .. }
+//   ::dynomite::FromAttributes::from_attrs(needed)
+// }
+fn get_from_attrs_ref_function(
+    container: &Container,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let from_attrs = quote!(::dynomite::FromAttributes::from_attrs);
+    let signature = quote! {
+        /// Converts borrowed attributes, such as those shared by an `Arc`, into this type,
+        /// cloning only the attributes its fields are resolved from
+        pub fn from_attrs_ref(
+            attrs: &::dynomite::Attributes
+        ) -> ::std::result::Result<Self, ::dynomite::AttributeError>
+    };
+    // every attribute may be needed, either to resolve unprefixed flattened fields
+    // or to be reported as unknown
+    let needs_all = container.deny_unknown_fields().is_some()
+        || fields
+            .iter()
+            .any(|field| field.is_flatten() && field.flatten_prefix().is_none());
+    if needs_all {
+        return quote! {
+            #signature {
+                #from_attrs(attrs.clone())
+            }
+        };
+    }
+    // wrapped fields are all resolved from their wrapper
+    if let Some((_, wrapper)) = container.wrapped() {
+        return quote! {
+            #signature {
+                let mut needed = ::dynomite::Attributes::new();
+                if let ::std::option::Option::Some(value) = attrs.get(#wrapper) {
+                    needed.insert(#wrapper.to_string(), value.clone());
+                }
+                #from_attrs(needed)
+            }
+        };
+    }
+    let prefixes = fields.iter().filter_map(ItemField::flatten_prefix);
+    let aliases = fields.iter().flat_map(ItemField::aliases);
+
+    quote! {
+        #signature {
+            let mut needed = ::dynomite::Attributes::new();
+            for name in Self::ATTRIBUTE_NAMES.iter().chain(&[#(#aliases),*]) {
+                if let ::std::option::Option::Some(value) = attrs.get(*name) {
+                    needed.insert(name.to_string(), value.clone());
+                }
+            }
+            #(
+                for (name, value) in attrs.iter().filter(|(name, _)| name.starts_with(#prefixes)) {
+                    needed.insert(name.clone(), value.clone());
+                }
+            )*
+            #from_attrs(needed)
+        }
+    }
+}
+
+// pub fn condition_expression_for_save(&self) -> (String, HashMap<String, String>, ::dynomite::Attributes) {
+//   let mut names = HashMap::new();
+//   names.insert("#version".to_string(), "version_deser_name".to_string());
+//   let mut values = ::dynomite::Attributes::new();
+//   values.insert(":expected".to_string(), ::dynomite::Attribute::into_attr(self.version.clone()));
+//   ("#version = :expected".to_string(), names, values)
+// }
+//
+// pub fn increment_version(&mut self) {
+//   self.version = self.version.wrapping_add(1);
+// }
+fn get_version_functions(fields: &[ItemField]) -> syn::Result<Option<impl ToTokens>> {
+    use syn::spanned::Spanned as _;
+    let versions = fields
+        .iter()
+        .filter_map(|field| field.version().map(|attr| (field, attr)))
+        .collect::<Vec<_>>();
+    let (field, attr) = match versions.as_slice() {
+        [] => return Ok(None),
+        [version] => *version,
+        [_, (_, attr), ..] => {
+            return Err(syn::Error::new(
+                attr.span(),
+                format!(
+                    "Only one version field may be declared. Found {}",
+                    versions.len()
+                ),
+            ))
+        }
+    };
+    if field.is_partition_key() || field.is_sort_key() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "version fields may not also be partition or sort keys",
+        ));
+    }
+    if !is_integer(&field.field.ty) {
+        return Err(syn::Error::new(
+            field.field.ty.span(),
+            "version fields must be a primitive integer type",
+        ));
+    }
+
+    let to_attribute_value = quote!(::dynomite::Attribute::into_attr);
+    let attributes = quote!(::dynomite::Attributes);
+    let string = quote!(::std::string::String);
+    let field_ident = &field.field.ident;
+    let field_deser_name = field.deser_name();
+    Ok(Some(quote! {
+        /// Returns a `ConditionExpression` along with its `ExpressionAttributeNames` and
+        /// `ExpressionAttributeValues` which only permit saving over a stored item whose
+        /// version matches this one's
+        #[must_use]
+        pub fn condition_expression_for_save(
+            &self
+        ) -> (#string, ::std::collections::HashMap<#string, #string>, #attributes) {
+            let mut names = ::std::collections::HashMap::new();
+            names.insert("#version".to_string(), #field_deser_name.to_string());
+            let mut values = #attributes::new();
+            values.insert(
+                ":expected".to_string(),
+                #to_attribute_value(self.#field_ident.clone())
+            );
+            ("#version = :expected".to_string(), names, values)
+        }
+
+        /// Increments this item's version, wrapping around to the integer type's
+        /// minimum value after its maximum
+        pub fn increment_version(&mut self) {
+            self.#field_ident = self.#field_ident.wrapping_add(1);
+        }
+    }))
+}
+
+// fn changed_fields(&self, other: &Self) -> Vec<&'static str> {
+//   let mut changed = Vec::new();
+//   if self.field != other.field {
+//     changed.push("field_deser_name");
+//   }
+//   ...
+//   changed
+// }
+fn get_changed_fields_function(
+    attr: &Ident,
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let comparisons = fields
+        .iter()
+        .filter(|field| !field.is_skipped())
+        .map(|field| {
+            if field.is_flatten() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!(
+                        "changed_fields does not support flattened field `{}`",
+                        field
+                            .field
+                            .ident
+                            .as_ref()
+                            .expect("should have an identifier")
+                    ),
+                ));
+            }
+            let field_ident = &field.field.ident;
+            if let Some(composite) = composite.filter(|c| c.contains(field)) {
+                // components share one attribute which is only reported once
+                let name = &composite.name;
+                return Ok(quote! {
+                    if self.#field_ident != other.#field_ident && !changed.contains(&#name) {
+                        changed.push(#name);
+                    }
+                });
+            }
+            let field_deser_name = field.deser_name();
+            let cfgs = field.cfg_attrs();
+            Ok(quote! {
+                #(#cfgs)*
+                if self.#field_ident != other.#field_ident {
+                    changed.push(#field_deser_name);
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        /// Returns the names of the attributes whose values differ from `other`
+        pub fn changed_fields(&self, other: &Self) -> ::std::vec::Vec<&'static str> {
+            let mut changed = ::std::vec::Vec::new();
+            #(#comparisons)*
+            changed
+        }
+    })
+}
+
+// pub fn merge(&mut self, other: Self) {
+//   if other.field != <FieldType as Default>::default() {
+//     self.field = other.field;
+//   }
+//   if other.option_field.is_some() {
+//     self.option_field = other.option_field;
+//   }
+//   ...
+// }
+fn get_merge_function(fields: &[ItemField]) -> impl ToTokens {
+    // key fields identify the item and are never overwritten
+    let fields = fields
+        .iter()
+        .filter(|field| {
+            !(field.is_skipped()
+                || field.is_partition_key()
+                || field.is_sort_key()
+                || field.key_delegate().is_some())
+        })
+        .collect::<Vec<_>>();
+    let overwrites = fields.iter().map(|field| {
+        let cfgs = field.cfg_attrs();
+        let field_ident = &field.field.ident;
+        let is_set = if field.is_option() {
+            quote!(other.#field_ident.is_some())
+        } else {
+            let field_default = get_default_value(&field.field.ty);
+            quote!(other.#field_ident != #field_default)
+        };
+        quote! {
+            #(#cfgs)*
+            if #is_set {
+                self.#field_ident = other.#field_ident;
+            }
+        }
+    });
+    // as with `fields`, higher-ranked bounds defer checking until the method is used
+    let bounds = fields
+        .iter()
+        .filter(|field| !field.is_option())
+        .map(|field| {
+            let field_ty = &field.field.ty;
+            quote!(for<'__dynomite> #field_ty: ::std::cmp::PartialEq + ::std::default::Default)
+        });
+
+    quote! {
+        /// Overwrites each field which is not part of the item's key with the value of
+        /// `other`'s, unless that is its type's `Default` value or `None`
+        #[allow(unused_variables)]
+        pub fn merge(&mut self, other: Self)
+        where
+            #(#bounds),*
+        {
+            #(#overwrites)*
+        }
+    }
+}
+
+// impl From<Name> for ::dynomite::Attributes {
+//    fn from(n: Name) ->  Self {
+//      ...
+//    }
+// }
+//
+// impl From<&Name> for ::dynomite::Attributes where for<'a> FieldType: Clone, ... {
+//    fn from(n: &Name) ->  Self {
+//      ...
+//    }
+// }
+//
+fn get_to_attribute_map_trait(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let attributes = quote!(::dynomite::Attributes);
+    let from = quote!(::std::convert::From);
+    let to_attribute_map = get_to_attribute_map_function(name, generics, container, fields)?;
+    let ref_to_attribute_map =
+        get_ref_to_attribute_map_function(name, generics, container, fields)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause
+        .into_iter()
+        .flat_map(|where_clause| where_clause.predicates.iter());
+    // the higher-ranked bounds defer checking until the impl is used
+    // so that types with fields lacking Clone impls still compile
+    let clone_bounds = fields
+        .iter()
+        .filter(|field| !field.is_skipped())
+        .map(|field| {
+            let field_ty = &field.field.ty;
+            quote!(for<'__dynomite> #field_ty: ::std::clone::Clone)
+        });
+
+    Ok(quote! {
+        impl #impl_generics #from<#name #ty_generics> for #attributes #where_clause {
+            #to_attribute_map
+        }
+
+        impl #impl_generics #from<&#name #ty_generics> for #attributes
+        where
+            #(#predicates,)*
+            #(#clone_bounds),*
+        {
+            #ref_to_attribute_map
+        }
+    })
+}
+
+// generates the `from(...)` method for attribute map From conversion
+//
+// fn from(item: Foo) -> Self {
+//   let mut values = Self::new();
+//   values.insert(
+//     "foo".to_string(),
+//     ::dynomite::Attribute::into_attr(item.field)
+//   );
+//   ...
+//   values
+// }
+fn get_to_attribute_map_function(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let field_conversions = get_to_attribute_map_inserts(container, fields, |field| {
+        let field_ident = &field.field.ident;
+        quote!(item.#field_ident)
+    })?;
+
+    let wrap = get_wrap_statement(container);
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    Ok(quote! {
+        fn from(item: #name #ty_generics) -> Self {
+            let mut values = Self::new();
+            #(#field_conversions)*
+            #wrap
+            values
+        }
+    })
+}
+
+// generates the `from(...)` method for borrowed attribute map From conversion
+//
+// fn from(item: &Foo) -> Self {
+//   let mut values = Self::new();
+//   values.insert(
+//     "foo".to_string(),
+//     ::dynomite::Attribute::into_attr(item.field.clone())
+//   );
+//   ...
+//   values
+// }
+fn get_ref_to_attribute_map_function(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let field_conversions = get_to_attribute_map_inserts(container, fields, |field| {
+        let field_ident = &field.field.ident;
+        quote!(::std::clone::Clone::clone(&item.#field_ident))
+    })?;
+
+    let wrap = get_wrap_statement(container);
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    Ok(quote! {
+        fn from(item: &#name #ty_generics) -> Self {
+            let mut values = Self::new();
+            #(#field_conversions)*
+            #wrap
+            values
+        }
+    })
+}
+
+// nests the collected `values` within the map attribute named by `wrapped`, if any
+//
+// let values = {
+//   let mut wrapper = ::dynomite::Attributes::new();
+//   wrapper.insert("wrapper".to_string(), ::dynomite::AttributeValue { m: Some(values), ..Default::default() });
+//   wrapper
+// };
+fn get_wrap_statement(container: &Container) -> Option<impl ToTokens> {
+    container.wrapped().map(|(_, wrapper)| {
+        quote! {
+            let values = {
+                let mut wrapper = ::dynomite::Attributes::new();
+                wrapper.insert(
+                    #wrapper.to_string(),
+                    ::dynomite::AttributeValue {
+                        m: ::std::option::Option::Some(values),
+                        ..::std::default::Default::default()
+                    },
+                );
+                wrapper
+            };
+        }
+    })
+}
+
+// generates the statements inserting each field into a `values` attribute map
+// where `access` resolves an expression for a field's value
+//
+// values.insert(
+//   "foo".to_string(),
+//   ::dynomite::Attribute::into_attr(item.field)
+// );
+fn get_to_attribute_map_inserts(
+    container: &Container,
+    fields: &[ItemField],
+    access: impl Fn(&ItemField) -> proc_macro2::TokenStream,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    get_attribute_inserts(container, fields, Sink::Map, false, access)
+}
+
+/// The collection generated statements add each attribute to as `values`
+#[derive(Clone, Copy)]
+enum Sink {
+    /// An `Attributes` map
+    Map,
+    /// A `Vec<(Cow<'static, str>, AttributeValue)>` in declaration order
+    Fields,
+}
+
+impl Sink {
+    // values.insert("foo".to_string(), value);
+    fn insert(
+        self,
+        name: impl ToTokens,
+        value: impl ToTokens,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            Sink::Map => quote! {
+                values.insert(
+                    #name.to_string(),
+                    #value
+                );
+            },
+            Sink::Fields => quote! {
+                values.push((::std::borrow::Cow::Borrowed(#name), #value));
+            },
+        }
+    }
+
+    // values.extend(attributes);
+    fn extend(
+        self,
+        attributes: impl ToTokens,
+    ) -> proc_macro2::TokenStream {
+        match self {
+            Sink::Map => quote! {
+                values.extend(#attributes);
+            },
+            Sink::Fields => quote! {
+                values.extend(
+                    #attributes
+                        .into_iter()
+                        .map(|(name, value)| (::std::borrow::Cow::Owned(name), value))
+                );
+            },
+        }
+    }
+}
+
+// generates the statements adding each field's attribute to `values`
+// where `access` resolves an expression for a field's value. `fallible`
+// statements convert values with `try_into_attr`, returning early on errors
+fn get_attribute_inserts(
+    container: &Container,
+    fields: &[ItemField],
+    sink: Sink,
+    fallible: bool,
+    access: impl Fn(&ItemField) -> proc_macro2::TokenStream,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    use syn::spanned::Spanned as _;
+    let composite = CompositeSortKey::new(container, fields)?;
+
+    // PhantomData markers and presence sets carry no data and are never stored,
+    // read only fields are never written
+    fields
+        .iter()
+        .filter(|field| !field.is_skipped() && field.never_written().is_none())
+        .map(|field| {
+            if let Some(composite) = composite.as_ref().filter(|c| c.contains(field)) {
+                // the first component inserts the joined value of them all
+                if !composite.is_first_declared(field, fields) {
+                    return Ok(proc_macro2::TokenStream::new());
+                }
+                let name = &composite.name;
+                let value = composite.join(&access);
+                return Ok(sink.insert(name, value));
+            }
+            validate_encoding(field)?;
+            let field_deser_name = field.deser_name();
+            let cfgs = field.cfg_attrs();
+
+            let field_value = access(field);
+            if field.is_flatten() {
+                // maps hoist each of their entries, converting values one by one
+                let flattened = if is_string_map(&field.field.ty) {
+                    if field.flatten_prefix().is_none() {
+                        return Err(syn::Error::new(
+                            field.field.span(),
+                            "flattened maps require a prefix, e.g. `#[dynomite(flatten(prefix = \"prefix_\"))]`",
+                        ));
+                    }
+                    if fallible {
+                        quote! {
+                            #field_value
+                                .into_iter()
+                                .map(|(name, value)| {
+                                    ::dynomite::Attribute::try_into_attr(value).map(|value| (name, value))
+                                })
+                                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+                        }
+                    } else {
+                        quote! {
+                            #field_value
+                                .into_iter()
+                                .map(|(name, value)| (name, ::dynomite::Attribute::into_attr(value)))
+                        }
+                    }
+                } else if fallible {
+                    // flattened types check their own fields
+                    quote!(#field_value.try_into_attributes()?)
+                } else {
+                    quote!(::dynomite::Attributes::from(#field_value))
+                };
+                let extend = match field.flatten_prefix() {
+                    Some(prefix) => sink.extend(quote! {
+                        #flattened
+                            .into_iter()
+                            .map(|(name, value)| (format!("{}{}", #prefix, name), value))
+                    }),
+                    None => sink.extend(flattened),
+                };
+                return Ok(quote! {
+                    #(#cfgs)*
+                    #extend
+                });
+            }
+            let field_ty = &field.field.ty;
+            let timestamp = quote!(::dynomite::Timestamp);
+            let field_value = if field.is_updated_at() {
+                quote! {
+                    {
+                        let _ = #field_value;
+                        <#field_ty as #timestamp>::now_timestamp()
+                    }
+                }
+            } else if field.is_created_at() {
+                quote! {
+                    {
+                        let value = #field_value;
+                        if #timestamp::is_unset(&value) {
+                            <#field_ty as #timestamp>::now_timestamp()
+                        } else {
+                            value
+                        }
+                    }
+                }
+            } else {
+                field_value
+            };
+            let field_value = get_field_encoding(field, field_value, fallible);
+            let insert = sink.insert(field_deser_name, field_value);
+            Ok(quote! {
+                #(#cfgs)*
+                #insert
+            })
+        })
+        .collect()
+}
+
+// converts a field's value into an AttributeValue using its declared encoding,
+// encrypted afterwards when the field declares `encrypt_with`, failing `fallible`
+// conversions rather than panicking on encryption errors. Fields declaring
+// `null_when_default` are stored as NULL, unencrypted, when they equal their `Default`
+//
+// ::dynomite::Attribute::into_attr(value)
+fn get_field_encoding(
+    field: &ItemField,
+    value: proc_macro2::TokenStream,
+    fallible: bool,
+) -> proc_macro2::TokenStream {
+    if field.null_when_default().is_some() {
+        let field_ty = &field.field.ty;
+        let encoded = get_encrypted_field_encoding(field, quote!(value), fallible);
+        return quote! {
+            {
+                let value = #value;
+                if value == <#field_ty as ::std::default::Default>::default() {
+                    ::dynomite::AttributeValue {
+                        null: ::std::option::Option::Some(true),
+                        ..::std::default::Default::default()
+                    }
+                } else {
+                    #encoded
+                }
+            }
+        };
+    }
+    get_encrypted_field_encoding(field, value, fallible)
+}
+
+fn get_encrypted_field_encoding(
+    field: &ItemField,
+    value: proc_macro2::TokenStream,
+    fallible: bool,
+) -> proc_macro2::TokenStream {
+    let encoded = get_field_plain_encoding(field, value, fallible);
+    match field.encrypt_with() {
+        Some((_, path)) if fallible => quote!(#path::encrypt(#encoded)?),
+        Some((_, path)) => {
+            let field_deser_name = field.deser_name();
+            quote! {
+                #path::encrypt(#encoded).unwrap_or_else(|err| {
+                    ::std::panic!("failed to encrypt the {} attribute: {}", #field_deser_name, err)
+                })
+            }
+        }
+        None => encoded,
+    }
+}
+
+fn get_field_plain_encoding(
+    field: &ItemField,
+    value: proc_macro2::TokenStream,
+    fallible: bool,
+) -> proc_macro2::TokenStream {
+    match field.map() {
+        Some(_) => quote! {
+            ::dynomite::AttributeValue {
+                m: ::std::option::Option::Some(::dynomite::Attributes::from(#value)),
+                ..::std::default::Default::default()
+            }
+        },
+        None if field.ttl().is_some() => quote!(::dynomite::ttl::into_attr(#value)),
+        None if field.epoch().is_some() => quote!(::dynomite::epoch::into_attr(#value)),
+        None if field.is_json() => quote!(::dynomite::json::into_attr(#value)),
+        None if field.compress().is_some() => {
+            quote!(::dynomite::compress::gzip::into_attr(#value))
+        }
+        None if fallible => quote!(::dynomite::Attribute::try_into_attr(#value)?),
+        None => quote!(::dynomite::Attribute::into_attr(#value)),
+    }
+}
+
+/// ```rust,ignore
+/// impl ::dynomite::FromAttributes for Name {
+///   fn from_attrs(mut item: ::dynomite::Attributes) -> Result<Self, ::dynomite::Error> {
+///     Ok(Self {
+///        field_name: ::dynomite::Attribute::from_attr(
+///           item.remove("field_deser_name").ok_or(Error::MissingField { name: "field_deser_name".into() })?
+///        )
+///      })
+///   }
+/// }
+/// ```
+fn get_from_attributes_trait(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let from_attrs = quote!(::dynomite::FromAttributes);
+    let from_attribute_map = get_from_attributes_function(container, fields)?;
+    let try_from = get_try_from_attributes_trait(name, generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #from_attrs for #name #ty_generics #where_clause {
+            #from_attribute_map
+        }
+
+        #try_from
+    })
+}
+
+/// ```rust,ignore
+/// impl ::std::convert::TryFrom<::dynomite::Attributes> for Name {
+///   type Error = ::dynomite::AttributeError;
+///   fn try_from(attrs: ::dynomite::Attributes) -> Result<Self, Self::Error> {
+///     ::dynomite::FromAttributes::from_attrs(attrs)
+///   }
+/// }
+/// ```
+fn get_try_from_attributes_trait(
+    name: &Ident,
+    generics: &Generics,
+) -> impl ToTokens {
+    let attributes = quote!(::dynomite::Attributes);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::std::convert::TryFrom<#attributes> for #name #ty_generics #where_clause {
+            type Error = ::dynomite::AttributeError;
+            fn try_from(attrs: #attributes) -> ::std::result::Result<Self, Self::Error> {
+                ::dynomite::FromAttributes::from_attrs(attrs)
+            }
+        }
+    }
+}
+
+fn get_from_attributes_function(
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let attributes = quote!(::dynomite::Attributes);
+    let err = quote!(::dynomite::AttributeError);
+    let field_conversions = get_from_attributes_conversions(container, fields)?;
+    let presence = get_presence_declaration(fields)?;
+
+    // wrapped fields are resolved from the map attribute they are nested within
+    let unwrap = container.wrapped().map(|(_, wrapper)| {
+        quote! {
+            let mut wrapped = attrs
+                .remove(#wrapper)
+                .ok_or_else(|| #err::MissingField {
+                    name: #wrapper.to_string(),
+                })?
+                .m
+                .ok_or(#err::InvalidType)?;
+            let attrs = &mut wrapped;
+        }
+    });
+    // fields are removed from the borrowed attributes as they are resolved
+    let from_attrs_mut = quote! {
+        fn from_attrs_mut(attrs: &mut #attributes) -> ::std::result::Result<Self, #err> {
+            #unwrap
+            #presence
+            ::std::result::Result::Ok(Self {
+                #(#field_conversions),*
+            })
+        }
+    };
+
+    let deny_unknown_fields = match container.deny_unknown_fields() {
+        Some(attr) => attr,
+        None => {
+            return Ok(quote! {
+                fn from_attrs(mut attrs: #attributes) -> ::std::result::Result<Self, #err> {
+                    Self::from_attrs_mut(&mut attrs)
+                }
+
+                #from_attrs_mut
+            })
+        }
+    };
+
+    if container.wrapped().is_some() {
+        return Err(syn::Error::new(
+            deny_unknown_fields.span(),
+            "deny_unknown_fields does not support wrapped Attributes",
+        ));
+    }
+
+    // an unprefixed flattened field sees every attribute so there is no way
+    // of telling which of those were actually unknown
+    if let Some(field) = fields
+        .iter()
+        .find(|field| field.is_flatten() && field.flatten_prefix().is_none())
+    {
+        return Err(syn::Error::new(
+            deny_unknown_fields.span(),
+            format!(
+                "deny_unknown_fields does not support flattened field `{}` without a prefix",
+                field
+                    .field
+                    .ident
+                    .as_ref()
+                    .expect("should have an identifier")
+            ),
+        ));
+    }
+
+    Ok(quote! {
+        fn from_attrs(mut attrs: #attributes) -> ::std::result::Result<Self, #err> {
+            let item = Self::from_attrs_mut(&mut attrs)?;
+            if let ::std::option::Option::Some(name) = attrs.keys().next() {
+                return ::std::result::Result::Err(#err::UnknownField { name: name.clone() });
+            }
+            ::std::result::Result::Ok(item)
+        }
+
+        #from_attrs_mut
+    })
+}
+
+// declares the set recording which tracked attributes were present, if any
+//
+// let mut presence = ::std::collections::HashSet::new();
+fn get_presence_declaration(fields: &[ItemField]) -> syn::Result<Option<impl ToTokens>> {
+    use syn::spanned::Spanned as _;
+    let presence_fields = fields
+        .iter()
+        .filter(|field| field.is_presence())
+        .collect::<Vec<_>>();
+    if let [_, field, ..] = presence_fields.as_slice() {
+        return Err(syn::Error::new(
+            field.field.span(),
+            format!(
+                "Only one presence field may be declared. Found {}",
+                presence_fields.len()
+            ),
+        ));
+    }
+    for field in fields {
+        let attr = match field.track_presence() {
+            Some(attr) => attr,
+            None => continue,
+        };
+        if presence_fields.is_empty() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "track_presence requires a `#[dynomite(presence)]` field of type `HashSet<&'static str>`",
+            ));
+        }
+        if field.is_flatten() || !(field.is_default_when_absent() || field.is_option()) {
+            return Err(syn::Error::new(
+                attr.span(),
+                "track_presence may only be applied to default or Option fields",
+            ));
+        }
+    }
+    Ok(presence_fields
+        .first()
+        .map(|_| quote!(let mut presence = ::std::collections::HashSet::new();)))
+}
+
+// generates each `field_name: value` initializer resolved from a mutable `attrs` attribute map
+//
+// field_name: ::dynomite::Attribute::from_attr(
+//   attrs.remove("field_deser_name").ok_or(Error::MissingField { name: "field_deser_name".into() })?
+// )?
+fn get_from_attributes_conversions(
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let attributes = quote!(::dynomite::Attributes);
+    let from_attribute_value = quote!(::dynomite::Attribute::from_attr);
+    let composite = CompositeSortKey::new(container, fields)?;
+    validate_attribute_names(composite.as_ref(), fields)?;
+    let is_component = |field: &ItemField| {
+        composite
+            .as_ref()
+            .map(|composite| composite.contains(field))
+            .unwrap_or(false)
+    };
+
+    // composite sort key components share one attribute, removed by the last of them.
+    // flattened fields are resolved last so they only see
+    // what remains after their siblings have been removed, unprefixed ones after prefixed ones,
+    // followed by the presence set once it has been populated
+    let ordered_fields = fields
+        .iter()
+        .filter(|field| !field.is_flatten() && !field.is_presence() && !is_component(field))
+        .chain(fields.iter().filter(|field| is_component(field)))
+        .chain(
+            fields
+                .iter()
+                .filter(|field| field.is_flatten() && field.flatten_prefix().is_some()),
+        )
+        .chain(
+            fields
+                .iter()
+                .filter(|field| field.is_flatten() && field.flatten_prefix().is_none()),
+        )
+        .chain(fields.iter().filter(|field| field.is_presence()));
+    let last_component = fields.iter().filter(|field| is_component(field)).last();
+
+    let conversion = |field: &ItemField| -> syn::Result<proc_macro2::TokenStream> {
+        let field_ident = &field.field.ident;
+        if let Some(composite) = composite.as_ref().filter(|c| c.contains(field)) {
+            let remove = last_component
+                .map(|last| std::ptr::eq(last.field, field.field))
+                .unwrap_or(false);
+            return Ok(composite.component(field, remove));
+        }
+        if field.is_phantom_data() {
+            return Ok(quote! {
+                #field_ident: ::std::marker::PhantomData
+            });
+        }
+        if field.is_presence() {
+            return Ok(quote! {
+                #field_ident: presence
+            });
+        }
+        validate_encoding(field)?;
+        // field has #[dynomite(renameField = "...")] attribute
+        let field_deser_name = field.deser_name();
+        let removal = get_field_removal(field);
+        let record_presence = field
+            .track_presence()
+            .map(|_| quote!(presence.insert(#field_deser_name);));
+        // stored values are discarded so that they are not reported as unknown
+        if let Some(skip) = field.skip_deserializing() {
+            if field.is_flatten() {
+                return Err(syn::Error::new(
+                    skip.span(),
+                    "skip_deserializing may not be applied to flatten fields",
+                ));
+            }
+            let field_default = get_field_default(field);
+            return Ok(quote! {
+                #field_ident: {
+                    #removal;
+                    #field_default
+                }
+            });
+        }
+        let from_attribute_value = match field.map() {
+            Some(_) => quote! {
+                (|value: ::dynomite::AttributeValue| value
+                    .m
+                    .ok_or(::dynomite::AttributeError::InvalidType)
+                    .and_then(::dynomite::FromAttributes::from_attrs))
+            },
+            None if field.ttl().is_some() => quote!(::dynomite::ttl::from_attr),
+            None if field.epoch().is_some() => quote!(::dynomite::epoch::from_attr),
+            // legacy numbers stored as strings are read as though they were stored as `N`
+            None if field.lenient_number().is_some() => quote! {
+                (|mut value: ::dynomite::AttributeValue| {
+                    if value.n.is_none() {
+                        value.n = value.s.take();
+                    }
+                    #from_attribute_value(value)
+                })
+            },
+            None if field.compress().is_some() => quote!(::dynomite::compress::gzip::from_attr),
+            None if field.is_json() => quote!(::dynomite::json::from_attr),
+            None => from_attribute_value.clone(),
+        };
+        // encrypted values are decrypted before they are parsed
+        let from_attribute_value = match field.encrypt_with() {
+            Some((_, path)) => quote! {
+                (|value: ::dynomite::AttributeValue| #path::decrypt(value).and_then(#from_attribute_value))
+            },
+            None => from_attribute_value,
+        };
+        // NULL values resolve the field's `Default` before they are decrypted
+        let from_attribute_value = match field.null_when_default() {
+            Some(_) => quote! {
+                (|value: ::dynomite::AttributeValue| {
+                    if value.null == ::std::option::Option::Some(true) {
+                        ::std::result::Result::Ok(::std::default::Default::default())
+                    } else {
+                        #from_attribute_value(value)
+                    }
+                })
+            },
+            None => from_attribute_value,
+        };
+
+        if field.is_flatten() {
+            let prefix = match field.flatten_prefix() {
+                Some(prefix) => prefix,
+                // unprefixed fields remove the attributes they consume, leaving the rest
+                None => {
+                    return Ok(quote! {
+                        #field_ident: ::dynomite::FromAttributes::from_attrs_mut(attrs)?
+                    })
+                }
+            };
+            let flattened = quote! {
+                {
+                    let names = attrs
+                        .keys()
+                        .filter(|name| name.starts_with(#prefix))
+                        .cloned()
+                        .collect::<::std::vec::Vec<_>>();
+                    let mut flattened = #attributes::new();
+                    for name in names {
+                        if let Some(value) = attrs.remove(&name) {
+                            flattened.insert(name[#prefix.len()..].to_string(), value);
+                        }
+                    }
+                    flattened
+                }
+            };
+            Ok(quote! {
+                #field_ident: ::dynomite::FromAttributes::from_attrs(#flattened)?
+            })
+        } else if field.is_default_on_error() {
+            let field_default = get_field_default(field);
+            Ok(quote! {
+                #field_ident: match #removal {
+                    Some(field) => {
+                        #record_presence
+                        #from_attribute_value(field).unwrap_or_else(|_| #field_default)
+                    }
+                    _ => #field_default
+                }
+            })
+        } else if field.is_default_when_absent() {
+            let field_default = get_field_default(field);
+            Ok(quote! {
+                #field_ident: match #removal {
+                    Some(field) => {
+                        #record_presence
+                        #from_attribute_value(field)?
+                    }
+                    _ => #field_default
+                }
+            })
+        } else if field.is_option() {
+            // absent and NULL attributes both resolve to None
+            Ok(quote! {
+                #field_ident: match #removal {
+                    Some(field) => {
+                        #record_presence
+                        #from_attribute_value(field)?
+                    }
+                    _ => ::std::option::Option::None
+                }
+            })
+        } else {
+            Ok(quote! {
+                #field_ident: #from_attribute_value(
+                    #removal
+                        .ok_or(::dynomite::AttributeError::MissingField { name: #field_deser_name.to_string() })?
+                )?
+            })
+        }
+    };
+    ordered_fields
+        .map(|field| {
+            let cfgs = field.cfg_attrs();
+            conversion(field).map(|init| quote!(#(#cfgs)* #init))
+        })
+        .collect()
+}
+
+fn get_dynomite_item_traits(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let impls = get_item_impls(vis, name, generics, container, fields)?;
+
+    Ok(quote! {
+        #impls
+    })
+}
+
+fn get_item_impls(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    // impl ::dynomite::Item for Name ...
+    let item_trait = get_item_trait(name, generics, container, fields)?;
+    // impl Name { fn put_input(..); fn get_input(..) }
+    let item_inputs = if cfg!(feature = "inputs") {
+        Some(get_item_input_functions(name, generics))
+    } else {
+        None
+    };
+    // impl Name { const TABLE_NAME: &'static str }
+    let table_name = get_table_name_const(name, generics, container);
+    // impl Name { fn schema() -> ::dynomite::Schema }
+    let schema = get_schema_function(name, generics, container, fields)?;
+    // impl Name { fn not_exists_condition() -> (String, HashMap<String, String>); .. }
+    let existence_conditions = get_existence_condition_functions(name, generics, fields);
+    // impl Name { fn key_attribute_names() -> &'static [&'static str] }
+    let key_attribute_names = get_key_attribute_names_function(name, generics, container, fields)?;
+    // impl Name { fn field_name_eq(value) -> (String, HashMap<String, String>, Attributes) }
+    let scan_filters = get_scan_filter_functions(name, generics, container, fields)?;
+    // pub struct NameQuery ...
+    let query_builder = get_query_builder(vis, name, generics, container, fields)?;
+    // impl Name { fn key_string(&self) -> String }
+    let key_string = get_item_key_string_function(name, generics, container, fields)?;
+    // impl Name { async fn get(..); async fn save(..); async fn delete(..) }
+    let crud = container
+        .crud()
+        .map(|attr| get_crud_functions(name, generics, container, attr))
+        .transpose()?;
+    // pub struct NameUpdate ...
+    let update_struct = container
+        .update_struct()
+        .map(|attr| get_update_struct(vis, name, generics, attr, fields))
+        .transpose()?;
+    // pub struct NameKey ...
+    let key_struct = if let Some((lit, path)) = container.key_struct() {
+        if container.skip_key_struct() || container.key_hash().is_some() {
+            return Err(syn::Error::new(
+                lit.span(),
+                "key_struct can not be combined with skip_key_struct or key_hash",
+            ));
+        }
+        if key_delegate(fields).is_some() {
+            return Err(syn::Error::new(
+                lit.span(),
+                "key_struct can not be combined with a key flatten field",
+            ));
+        }
+        // a struct literal of the shared key fails to compile unless its fields match
+        Some(get_key_struct_conversion(name, generics, &path, fields).into_token_stream())
+    } else if container.skip_key_struct() {
+        if let Some(key_hash) = container.key_hash() {
+            return Err(syn::Error::new(
+                key_hash.span(),
+                "key_hash can not be combined with skip_key_struct",
+            ));
+        }
+        None
+    } else if let Some(delegate) = key_delegate(fields) {
+        if let Some(key_hash) = container.key_hash() {
+            return Err(syn::Error::new(
+                key_hash.span(),
+                "key_hash must be declared by the key flatten field's type",
+            ));
+        }
+        Some(get_delegated_key_struct(vis, name, generics, delegate)?.into_token_stream())
+    } else {
+        Some(get_key_struct(vis, name, generics, container, fields)?.into_token_stream())
+    };
+
+    Ok(quote! {
+        #item_trait
+        #item_inputs
+        #table_name
+        #existence_conditions
+        #key_attribute_names
+        #schema
+        #scan_filters
+        #query_builder
+        #key_string
+        #crud
+        #update_struct
+        #key_struct
+    })
+}
+
+/// ```rust,ignore
+/// #[derive(Debug, Clone, PartialEq, Default)]
+/// pub struct NameUpdate {
+///    non_key_field: Option<FieldType>,
+/// }
+///
+/// impl NameUpdate {
+///   pub fn update_expression(&self) -> (String, ::dynomite::Attributes) {
+///     let mut sets = Vec::new();
+///     let mut removes = Vec::new();
+///     let mut values = ::dynomite::Attributes::new();
+///     if let Some(value) = &self.non_key_field {
+///       sets.push("field_deser_name = :non_key_field");
+///       values.insert(":non_key_field".to_string(), to_attribute_value(value.clone()));
+///     }
+///     ...
+///     ("SET field_deser_name = :non_key_field", values)
+///   }
+/// }
+/// ```
+fn get_update_struct(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    attr: &Ident,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    use syn::{ext::IdentExt as _, spanned::Spanned as _};
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "update_struct does not support generic items",
+        ));
+    }
+    let update_name = Ident::new(&format!("{}Update", name), Span::call_site());
+    let update_fields = fields
+        .iter()
+        .filter(|field| {
+            // encrypted values could only be written by panicking on encryption errors
+            !(field.is_partition_key()
+                || field.is_sort_key()
+                || field.is_skipped()
+                || field.never_written().is_some()
+                || field.encrypt_with().is_some())
+        })
+        .map(|field| {
+            if field.is_flatten() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!(
+                        "update_struct does not support flattened field `{}`",
+                        field
+                            .field
+                            .ident
+                            .as_ref()
+                            .expect("should have an identifier")
+                    ),
+                ));
+            }
+            validate_encoding(field)?;
+            Ok(field)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let declarations = update_fields.iter().map(|field| {
+        let cfgs = field.cfg_attrs();
+        let field_vis = &field.field.vis;
+        let field_ident = &field.field.ident;
+        let field_ty = &field.field.ty;
+        quote!(#(#cfgs)* #field_vis #field_ident: ::std::option::Option<#field_ty>)
+    });
+    let assignments = update_fields.iter().map(|field| {
+        let field_ident = &field.field.ident;
+        let field_deser_name = field.deser_name();
+        let placeholder = format!(
+            ":{}",
+            field_ident
+                .as_ref()
+                .expect("should have an identifier")
+                .unraw()
+        );
+        let set = format!("{} = {}", field_deser_name, placeholder);
+        let value = get_field_encoding(field, quote!(value.clone()), false);
+        let set_value = quote! {
+            sets.push(#set);
+            values.insert(#placeholder.to_string(), #value);
+        };
+        let cfgs = field.cfg_attrs();
+        if field.is_option() {
+            // explicitly clearing an optional field removes its attribute
+            quote! {
+                #(#cfgs)*
+                match &self.#field_ident {
+                    ::std::option::Option::Some(::std::option::Option::None) => removes.push(#field_deser_name),
+                    ::std::option::Option::Some(value) => {
+                        #set_value
+                    }
+                    ::std::option::Option::None => (),
+                }
+            }
+        } else {
+            quote! {
+                #(#cfgs)*
+                if let ::std::option::Option::Some(value) = &self.#field_ident {
+                    #set_value
+                }
+            }
+        }
+    });
+    let attributes = quote!(::dynomite::Attributes);
+
+    Ok(quote! {
+        /// Optional non-key fields of an update to an item, applied when `Some`
+        #[derive(Debug, Clone, PartialEq, Default)]
+        #vis struct #update_name {
+            #(#declarations),*
+        }
+
+        impl #update_name {
+            /// Returns an `UpdateExpression` and its values which `SET` each field which is `Some`,
+            /// or `REMOVE`s optional fields which are `Some(None)`.
+            /// The expression is empty when no fields are `Some`
+            #[must_use]
+            #[allow(unused_mut)]
+            pub fn update_expression(&self) -> (::std::string::String, #attributes) {
+                let mut sets: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+                let mut removes: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+                let mut values = #attributes::new();
+                #(#assignments)*
+                let mut clauses = ::std::vec::Vec::new();
+                if !sets.is_empty() {
+                    clauses.push(format!("SET {}", sets.join(", ")));
+                }
+                if !removes.is_empty() {
+                    clauses.push(format!("REMOVE {}", removes.join(", ")));
+                }
+                (clauses.join(" "), values)
+            }
+        }
+    })
+}
+
+/// ```rust,ignore
+/// impl Name {
+///   pub const TABLE_NAME: &'static str = "table";
+/// }
+/// ```
+fn get_table_name_const(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+) -> Option<impl ToTokens> {
+    let table = container.table()?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The name of the table this item is stored in
+            pub const TABLE_NAME: &'static str = #table;
         }
-        _ => panic!("Dynomite Attributes can only be generated for enum types"),
-    }
+    })
 }
 
 /// ```rust,ignore
-/// impl ::dynomite::Attribute for Name {
-///   fn into_attr(self) -> ::dynomite::dynamodb::AttributeValue {
-///     let arm = match self {
-///        Name::Variant => "Variant".to_string()
-///     };
-///     ::dynomite::dynamodb::AttributeValue {
-///        s: Some(arm),
-///        ..Default::default()
+/// impl Name {
+///   pub fn put_input(self, table: impl Into<String>) -> ::dynomite::dynamodb::PutItemInput {
+///     ::dynomite::dynamodb::PutItemInput {
+///       table_name: table.into(),
+///       item: self.into(),
+///       ..Default::default()
 ///     }
 ///   }
-///   fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
-///     value.s.ok_or(::dynomite::AttributeError::InvalidType)
-///       .and_then(|value| match &value[..] {
-///          "Variant" => Ok(Name::Variant),
-///          _ => Err(::dynomite::AttributeError::InvalidFormat)
-///       })
+///   pub fn get_input(&self, table: impl Into<String>) -> ::dynomite::dynamodb::GetItemInput {
+///     ::dynomite::dynamodb::GetItemInput {
+///       table_name: table.into(),
+///       key: ::dynomite::Item::key(self),
+///       ..Default::default()
+///     }
 ///   }
 /// }
 /// ```
-fn make_dynomite_attr(
+fn get_item_input_functions(
     name: &Ident,
-    variants: &[Variant],
+    generics: &Generics,
 ) -> impl ToTokens {
-    let attr = quote!(::dynomite::Attribute);
-    let err = quote!(::dynomite::AttributeError);
-    let into_match_arms = variants.iter().map(|var| {
-        let vname = &var.ident;
-        quote! {
-            #name::#vname => stringify!(#vname).to_string(),
-        }
-    });
-    let from_match_arms = variants.iter().map(|var| {
-        let vname = &var.ident;
-        quote! {
-            stringify!(#vname) => ::std::result::Result::Ok(#name::#vname),
-        }
-    });
-
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let string = quote!(::std::string::String);
     quote! {
-        impl #attr for #name {
-            fn into_attr(self) -> ::dynomite::dynamodb::AttributeValue {
-                let arm = match self {
-                    #(#into_match_arms)*
-                };
-                ::dynomite::dynamodb::AttributeValue {
-                    s: ::std::option::Option::Some(arm),
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a `PutItemInput` storing this item in the named table
+            #[must_use]
+            pub fn put_input(
+                self,
+                table: impl ::std::convert::Into<#string>
+            ) -> ::dynomite::dynamodb::PutItemInput {
+                ::dynomite::dynamodb::PutItemInput {
+                    table_name: table.into(),
+                    item: self.into(),
                     ..::std::default::Default::default()
                 }
             }
-            fn from_attr(value: ::dynomite::dynamodb::AttributeValue) -> ::std::result::Result<Self, #err> {
-                value.s.ok_or(::dynomite::AttributeError::InvalidType)
-                    .and_then(|value| match &value[..] {
-                        #(#from_match_arms)*
-                        _ => ::std::result::Result::Err(::dynomite::AttributeError::InvalidFormat)
-                    })
+
+            /// Returns a `GetItemInput` fetching this item by key from the named table
+            #[must_use]
+            pub fn get_input(
+                &self,
+                table: impl ::std::convert::Into<#string>
+            ) -> ::dynomite::dynamodb::GetItemInput {
+                ::dynomite::dynamodb::GetItemInput {
+                    table_name: table.into(),
+                    key: ::dynomite::Item::key(self),
+                    ..::std::default::Default::default()
+                }
             }
         }
     }
 }
 
-fn expand_attributes(ast: DeriveInput) -> syn::Result<impl ToTokens> {
-    use syn::spanned::Spanned as _;
-    let name = &ast.ident;
-    match ast.data {
-        Struct(DataStruct { fields, .. }) => match fields {
-            Fields::Named(named) => {
-                make_dynomite_attributes(name, &named.named.into_iter().collect::<Vec<_>>())
-            }
-            fields => Err(syn::Error::new(
-                fields.span(),
-                "Dynomite Attributes require named fields",
-            )),
-        },
-        _ => panic!("Dynomite Attributes can only be generated for structs"),
+/// ```rust,ignore
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct NameQuery {
+///   partition: ::dynomite::AttributeValue,
+///   sort: Option<(&'static str, ::dynomite::Attributes)>,
+///   limit: Option<i64>,
+///   index_name: Option<String>,
+///   scan_index_forward: Option<bool>,
+/// }
+///
+/// impl NameQuery {
+///   pub fn partition(value: PartitionKeyType) -> Self { .. }
+///   pub fn sort_eq(self, value: SortKeyType) -> Self { .. }
+///   pub fn sort_between(self, start: SortKeyType, end: SortKeyType) -> Self { .. }
+///   // string and composite sort keys only
+///   pub fn sort_begins_with(self, prefix: &str) -> Self { .. }
+///   pub fn limit(self, limit: i64) -> Self { .. }
+///   pub fn index(self, name: impl Into<String>) -> Self { .. }
+///   pub fn scan_forward(self, scan_forward: bool) -> Self { .. }
+///   pub fn into_input(self, table: impl Into<String>) -> ::dynomite::dynamodb::QueryInput { .. }
+/// }
+/// ```
+fn get_query_builder(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<Option<impl ToTokens>> {
+    // key types of generic items may refer to type parameters the builder does not declare
+    // and the key types of delegated keys are not known
+    if container.skip_query_struct()
+        || !generics.params.is_empty()
+        || key_delegate(fields).is_some()
+    {
+        return Ok(None);
     }
-}
+    let query_name = Ident::new(&format!("{}Query", name), Span::call_site());
+    let to_attribute_value = quote!(::dynomite::Attribute::into_attr);
+    let attributes = quote!(::dynomite::Attributes);
+    let string = quote!(::std::string::String);
+    let option = quote!(::std::option::Option);
 
-fn expand_item(ast: DeriveInput) -> syn::Result<impl ToTokens> {
-    use syn::spanned::Spanned as _;
-    let name = &ast.ident;
-    let vis = &ast.vis;
-    match ast.data {
-        Struct(DataStruct { fields, .. }) => match fields {
-            Fields::Named(named) => {
-                make_dynomite_item(vis, name, &named.named.into_iter().collect::<Vec<_>>())
-            }
-            fields => Err(syn::Error::new(
-                fields.span(),
-                "Dynomite Items require named fields",
-            )),
-        },
-        _ => panic!("Dynomite Items can only be generated for structs"),
-    }
-}
+    let partition_key = fields
+        .iter()
+        .find(|field| field.is_partition_key())
+        .expect("items declare one partition_key");
+    let partition_key_name = partition_key.deser_name();
+    let partition_key_ty = &partition_key.field.ty;
 
-fn make_dynomite_attributes(
-    name: &Ident,
-    fields: &[Field],
-) -> syn::Result<impl ToTokens> {
-    let item_fields = fields.iter().map(ItemField::new).collect::<Vec<_>>();
-    // impl ::dynomite::FromAttributes for Name
-    let from_attribute_map = get_from_attributes_trait(name, &item_fields)?;
-    // impl From<Name> for ::dynomite::Attributes
-    let to_attribute_map = get_to_attribute_map_trait(name, &item_fields)?;
-    // impl Attribute for Name (these are essentially just a map)
-    let attribute = quote!(::dynomite::Attribute);
-    let impl_attribute = quote! {
-        impl #attribute for #name {
-            fn into_attr(self: Self) -> ::dynomite::AttributeValue {
-                ::dynomite::AttributeValue {
-                    m: Some(self.into()),
-                    ..::dynomite::AttributeValue::default()
+    // (attribute name, typed value type, whether values may be prefixes)
+    let sort_key = match CompositeSortKey::new(container, fields)? {
+        Some(composite) => Some((composite.name, None, true)),
+        None => fields
+            .iter()
+            .find(|field| field.is_sort_key())
+            .map(|field| {
+                let field_ty = &field.field.ty;
+                (field.deser_name(), Some(field_ty), is_string(field_ty))
+            }),
+    };
+
+    let sort_field = sort_key
+        .as_ref()
+        .map(|_| quote!(sort: #option<(&'static str, #attributes)>,));
+    let sort_init = sort_key.as_ref().map(|_| quote!(sort: #option::None,));
+    let sort_functions = sort_key.as_ref().map(|(_, sort_key_ty, is_prefixable)| {
+        let typed = sort_key_ty.map(|sort_key_ty| {
+            quote! {
+                /// Restricts results to the item whose sort key is equal to `value`
+                pub fn sort_eq(mut self, value: #sort_key_ty) -> Self {
+                    let mut values = #attributes::new();
+                    values.insert(":sk".to_string(), #to_attribute_value(value));
+                    self.sort = #option::Some(("#sk = :sk", values));
+                    self
+                }
+
+                /// Restricts results to items whose sort keys are between `start` and `end`, inclusive
+                pub fn sort_between(mut self, start: #sort_key_ty, end: #sort_key_ty) -> Self {
+                    let mut values = #attributes::new();
+                    values.insert(":sk_start".to_string(), #to_attribute_value(start));
+                    values.insert(":sk_end".to_string(), #to_attribute_value(end));
+                    self.sort = #option::Some(("#sk BETWEEN :sk_start AND :sk_end", values));
+                    self
                 }
             }
-            fn from_attr(value: ::dynomite::AttributeValue) -> Result<Self, ::dynomite::AttributeError> {
-                use ::dynomite::FromAttributes;
-                value
-                    .m
-                    .ok_or(::dynomite::AttributeError::InvalidType)
-                    .and_then(Self::from_attrs)
+        });
+        let begins_with = if *is_prefixable {
+            Some(quote! {
+                /// Restricts results to items whose sort keys begin with `prefix`
+                pub fn sort_begins_with(mut self, prefix: &str) -> Self {
+                    let mut values = #attributes::new();
+                    values.insert(":sk".to_string(), #to_attribute_value(prefix.to_string()));
+                    self.sort = #option::Some(("begins_with(#sk, :sk)", values));
+                    self
+                }
+            })
+        } else {
+            None
+        };
+        quote! {
+            #typed
+            #begins_with
+        }
+    });
+    let sort_condition = sort_key.as_ref().map(|(sort_key_name, ..)| {
+        quote! {
+            if let #option::Some((condition, sort_values)) = self.sort {
+                key_condition.push_str(" AND ");
+                key_condition.push_str(condition);
+                names.insert("#sk".to_string(), #sort_key_name.to_string());
+                values.extend(sort_values);
             }
         }
-    };
+    });
 
-    Ok(quote! {
-        #from_attribute_map
-        #to_attribute_map
-        #impl_attribute
-    })
-}
+    Ok(Some(quote! {
+        /// A builder of `QueryInput`s for this item's table
+        #[derive(Debug, Clone, PartialEq)]
+        #vis struct #query_name {
+            partition: ::dynomite::AttributeValue,
+            #sort_field
+            limit: #option<i64>,
+            index_name: #option<#string>,
+            scan_index_forward: #option<bool>,
+        }
 
-fn make_dynomite_item(
-    vis: &Visibility,
-    name: &Ident,
-    fields: &[Field],
-) -> syn::Result<impl ToTokens> {
-    let item_fields = fields.iter().map(ItemField::new).collect::<Vec<_>>();
-    // all items must have 1 primary_key
-    let partition_key_count = item_fields.iter().filter(|f| f.is_partition_key()).count();
-    if partition_key_count != 1 {
-        return Err(syn::Error::new(
-            name.span(),
-            format!(
-                "All Item's must declare one and only one partition_key. The `{}` Item declared {}",
-                name, partition_key_count
-            ),
-        ));
-    }
-    // impl Item for Name + NameKey struct
-    let dynamodb_traits = get_dynomite_item_traits(vis, name, &item_fields)?;
-    // impl ::dynomite::FromAttributes for Name
-    let from_attribute_map = get_from_attributes_trait(name, &item_fields)?;
-    // impl From<Name> for ::dynomite::Attributes
-    let to_attribute_map = get_to_attribute_map_trait(name, &item_fields)?;
+        impl #query_name {
+            /// Returns a query for items stored under a partition key
+            pub fn partition(value: #partition_key_ty) -> Self {
+                Self {
+                    partition: #to_attribute_value(value),
+                    #sort_init
+                    limit: #option::None,
+                    index_name: #option::None,
+                    scan_index_forward: #option::None,
+                }
+            }
 
-    Ok(quote! {
-        #from_attribute_map
-        #to_attribute_map
-        #dynamodb_traits
-    })
-}
+            #sort_functions
 
-// impl From<Name> for ::dynomite::Attributes {
-//    fn from(n: Name) ->  Self {
-//      ...
-//    }
-// }
-//
-fn get_to_attribute_map_trait(
-    name: &Ident,
-    fields: &[ItemField],
-) -> syn::Result<impl ToTokens> {
-    let attributes = quote!(::dynomite::Attributes);
-    let from = quote!(::std::convert::From);
-    let to_attribute_map = get_to_attribute_map_function(name, fields)?;
+            /// Limits the number of items evaluated per page of results
+            pub fn limit(mut self, limit: i64) -> Self {
+                self.limit = #option::Some(limit);
+                self
+            }
 
-    Ok(quote! {
-        impl #from<#name> for #attributes {
-            #to_attribute_map
+            /// Queries a secondary index rather than the table itself
+            pub fn index(mut self, name: impl ::std::convert::Into<#string>) -> Self {
+                self.index_name = #option::Some(name.into());
+                self
+            }
+
+            /// Returns results in ascending sort key order when true and descending order when false
+            pub fn scan_forward(mut self, scan_forward: bool) -> Self {
+                self.scan_index_forward = #option::Some(scan_forward);
+                self
+            }
+
+            /// Returns a `QueryInput` for the named table
+            #[must_use]
+            pub fn into_input(
+                self,
+                table: impl ::std::convert::Into<#string>
+            ) -> ::dynomite::dynamodb::QueryInput {
+                let mut key_condition = "#pk = :pk".to_string();
+                let mut names = ::std::collections::HashMap::new();
+                names.insert("#pk".to_string(), #partition_key_name.to_string());
+                let mut values = #attributes::new();
+                values.insert(":pk".to_string(), self.partition);
+                #sort_condition
+                ::dynomite::dynamodb::QueryInput {
+                    table_name: table.into(),
+                    key_condition_expression: #option::Some(key_condition),
+                    expression_attribute_names: #option::Some(names),
+                    expression_attribute_values: #option::Some(values),
+                    limit: self.limit,
+                    index_name: self.index_name,
+                    scan_index_forward: self.scan_index_forward,
+                    ..::std::default::Default::default()
+                }
+            }
         }
-    })
+    }))
 }
 
-// generates the `from(...)` method for attribute map From conversion
-//
-// fn from(item: Foo) -> Self {
-//   let mut values = Self::new();
-//   values.insert(
-//     "foo".to_string(),
-//     ::dynomite::Attribute::into_attr(item.field)
-//   );
-//   ...
-//   values
-// }
-fn get_to_attribute_map_function(
+/// ```rust,ignore
+/// impl Name {
+///   pub fn field_name_eq(value: FieldType) -> (String, HashMap<String, String>, ::dynomite::Attributes) {
+///     let mut names = HashMap::new();
+///     names.insert("#field_name".to_string(), "field_deser_name".to_string());
+///     let mut values = ::dynomite::Attributes::new();
+///     values.insert(":field_name".to_string(), to_attribute_value(value));
+///     ("#field_name = :field_name".to_string(), names, values)
+///   }
+///   // string sort keys only
+///   pub fn field_name_begins_with(prefix: &str) -> (String, HashMap<String, String>, ::dynomite::Attributes) {
+///     ...
+///     ("begins_with(#field_name, :field_name)".to_string(), names, values)
+///   }
+/// }
+/// ```
+fn get_scan_filter_functions(
     name: &Ident,
+    generics: &Generics,
+    container: &Container,
     fields: &[ItemField],
-) -> syn::Result<impl ToTokens> {
-    let to_attribute_value = quote!(::dynomite::Attribute::into_attr);
-
-    let field_conversions = fields
+) -> syn::Result<Option<impl ToTokens>> {
+    use syn::ext::IdentExt as _;
+    if container.skip_scan_filters() {
+        return Ok(None);
+    }
+    let composite = CompositeSortKey::new(container, fields)?;
+    let string = quote!(::std::string::String);
+    let filter = quote! {
+        (#string, ::std::collections::HashMap<#string, #string>, ::dynomite::Attributes)
+    };
+    let functions = fields
         .iter()
+        .filter(|field| {
+            // encrypted values could only be filtered on by panicking on encryption errors
+            !(field.is_skipped()
+                || field.is_flatten()
+                || field.encrypt_with().is_some()
+                || composite
+                    .as_ref()
+                    .map(|composite| composite.contains(field))
+                    .unwrap_or(false))
+        })
         .map(|field| {
+            let field_ident = field
+                .field
+                .ident
+                .as_ref()
+                .expect("should have an identifier");
+            let field_ty = &field.field.ty;
             let field_deser_name = field.deser_name();
-
-            let field_ident = &field.field.ident;
-            Ok(quote! {
-                values.insert(
-                    #field_deser_name.to_string(),
-                    #to_attribute_value(item.#field_ident)
+            let name_placeholder = format!("#{}", field_ident.unraw());
+            let value_placeholder = format!(":{}", field_ident.unraw());
+            let fragment = |function: &str,
+                            expression: String,
+                            param: proc_macro2::TokenStream,
+                            value: proc_macro2::TokenStream| {
+                let function = Ident::new(
+                    &format!("{}_{}", field_ident.unraw(), function),
+                    field_ident.span(),
                 );
-            })
-        })
-        .collect::<syn::Result<Vec<_>>>()?;
+                quote! {
+                    /// Returns a `FilterExpression` fragment along with its names and values
+                    #[must_use]
+                    pub fn #function(#param) -> #filter {
+                        let mut names = ::std::collections::HashMap::new();
+                        names.insert(#name_placeholder.to_string(), #field_deser_name.to_string());
+                        let mut values = ::dynomite::Attributes::new();
+                        values.insert(#value_placeholder.to_string(), #value);
+                        (#expression.to_string(), names, values)
+                    }
+                }
+            };
+            let eq = fragment(
+                "eq",
+                format!("{} = {}", name_placeholder, value_placeholder),
+                quote!(value: #field_ty),
+                get_field_encoding(field, quote!(value), false),
+            );
+            let begins_with = if field.is_sort_key() && is_string(field_ty) {
+                Some(fragment(
+                    "begins_with",
+                    format!("begins_with({}, {})", name_placeholder, value_placeholder),
+                    quote!(prefix: &str),
+                    quote!(::dynomite::Attribute::into_attr(prefix.to_string())),
+                ))
+            } else {
+                None
+            };
+            quote! {
+                #eq
+                #begins_with
+            }
+        });
 
-    Ok(quote! {
-        fn from(item: #name) -> Self {
-            let mut values = Self::new();
-            #(#field_conversions)*
-            values
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(Some(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#functions)*
         }
-    })
+    }))
 }
 
 /// ```rust,ignore
-/// impl ::dynomite::FromAttributes for Name {
-///   fn from_attrs(mut item: ::dynomite::Attributes) -> Result<Self, ::dynomite::Error> {
-///     Ok(Self {
-///        field_name: ::dynomite::Attribute::from_attr(
-///           item.remove("field_deser_name").ok_or(Error::MissingField { name: "field_deser_name".into() })?
-///        )
-///      })
-///   }
+/// impl Name {
+///   pub async fn get<C>(client: &C, key: impl Into<::dynomite::Attributes>) -> Result<Option<Self>, CrudError<GetItemError>> { .. }
+///   pub async fn save<C>(&self, client: &C) -> Result<(), CrudError<PutItemError>> { .. }
+///   pub async fn delete<C>(&self, client: &C) -> Result<(), CrudError<DeleteItemError>> { .. }
 /// }
 /// ```
-fn get_from_attributes_trait(
+fn get_crud_functions(
     name: &Ident,
-    fields: &[ItemField],
+    generics: &Generics,
+    container: &Container,
+    attr: &Ident,
 ) -> syn::Result<impl ToTokens> {
-    let from_attrs = quote!(::dynomite::FromAttributes);
-    let from_attribute_map = get_from_attributes_function(fields)?;
+    if !cfg!(feature = "crud") {
+        return Err(syn::Error::new(
+            attr.span(),
+            "crud requires dynomite's `crud` cargo feature",
+        ));
+    }
+    let table = container.table().ok_or_else(|| {
+        syn::Error::new(
+            attr.span(),
+            "crud requires a `#[dynomite(table = \"...\")]` container attribute",
+        )
+    })?;
+    let dynamodb = quote!(::dynomite::dynamodb);
+    let error = quote!(::dynomite::crud::CrudError);
+    let result = quote!(::std::result::Result);
 
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     Ok(quote! {
-        impl #from_attrs for #name {
-            #from_attribute_map
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Fetches the item stored under a key, if any
+            pub async fn get<C: #dynamodb::DynamoDb + ?::std::marker::Sized>(
+                client: &C,
+                key: impl ::std::convert::Into<::dynomite::Attributes>
+            ) -> #result<::std::option::Option<Self>, #error<#dynamodb::GetItemError>> {
+                let output = client
+                    .get_item(#dynamodb::GetItemInput {
+                        table_name: #table.into(),
+                        key: key.into(),
+                        ..::std::default::Default::default()
+                    })
+                    .await?;
+                output
+                    .item
+                    .map(::dynomite::FromAttributes::from_attrs)
+                    .transpose()
+                    .map_err(::std::convert::From::from)
+            }
+
+            /// Stores this item, replacing any item stored under its key
+            pub async fn save<C: #dynamodb::DynamoDb + ?::std::marker::Sized>(
+                &self,
+                client: &C
+            ) -> #result<(), #error<#dynamodb::PutItemError>>
+            where
+                Self: ::std::clone::Clone,
+            {
+                client.put_item(self.clone().put_input(#table)).await?;
+                ::std::result::Result::Ok(())
+            }
+
+            /// Deletes the item stored under this item's key
+            pub async fn delete<C: #dynamodb::DynamoDb + ?::std::marker::Sized>(
+                &self,
+                client: &C
+            ) -> #result<(), #error<#dynamodb::DeleteItemError>> {
+                client
+                    .delete_item(#dynamodb::DeleteItemInput {
+                        table_name: #table.into(),
+                        key: ::dynomite::Item::key(self),
+                        ..::std::default::Default::default()
+                    })
+                    .await?;
+                ::std::result::Result::Ok(())
+            }
         }
     })
 }
 
-fn get_from_attributes_function(fields: &[ItemField]) -> syn::Result<impl ToTokens> {
-    let attributes = quote!(::dynomite::Attributes);
-    let from_attribute_value = quote!(::dynomite::Attribute::from_attr);
-    let err = quote!(::dynomite::AttributeError);
-
-    let field_conversions = fields.iter().map(|field| {
-        // field has #[dynomite(renameField = "...")] attribute
-        let field_deser_name = field.deser_name();
-
-        let field_ident = &field.field.ident;
-        if field.is_default_when_absent() {
-            Ok(quote! {
-                #field_ident: match attrs.remove(#field_deser_name) {
-                    Some(field) => #from_attribute_value(field)?,
-                    _ => ::std::default::Default::default()
+/// ```rust,ignore
+/// impl Name {
+///   pub fn not_exists_condition() -> (String, HashMap<String, String>) {
+///     ("attribute_not_exists(#pk)".to_string(), names) // "#pk" => "partition_key_deser_name"
+///   }
+///   pub fn exists_condition() -> (String, HashMap<String, String>) {
+///     ("attribute_exists(#pk)".to_string(), names)
+///   }
+/// }
+/// ```
+fn get_existence_condition_functions(
+    name: &Ident,
+    generics: &Generics,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (not_exists, exists) = match key_delegate(fields) {
+        Some(delegate) => {
+            let delegate_ty = &delegate.field.ty;
+            (
+                quote!(<#delegate_ty>::not_exists_condition()),
+                quote!(<#delegate_ty>::exists_condition()),
+            )
+        }
+        None => {
+            let partition_key = fields
+                .iter()
+                .find(|field| field.is_partition_key())
+                .map(ItemField::deser_name)
+                .unwrap_or_default();
+            // the attribute name is substituted so that reserved words may be used
+            let condition = |function: &str| {
+                let expression = format!("{}(#pk)", function);
+                quote! {
+                    let mut names = ::std::collections::HashMap::new();
+                    names.insert("#pk".to_string(), #partition_key.to_string());
+                    (#expression.to_string(), names)
                 }
-            })
-        } else {
-            Ok(quote! {
-                #field_ident: #from_attribute_value(
-                    attrs.remove(#field_deser_name)
-                        .ok_or(::dynomite::AttributeError::MissingField { name: #field_deser_name.to_string() })?
-                )?
-            })
+            };
+            (
+                condition("attribute_not_exists"),
+                condition("attribute_exists"),
+            )
         }
-    }).collect::<syn::Result<Vec<_>>>()?;
+    };
+    let string = quote!(::std::string::String);
+    let condition = quote! {
+        (#string, ::std::collections::HashMap<#string, #string>)
+    };
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a `ConditionExpression` and its `ExpressionAttributeNames` which hold
+            /// when no item is stored under the key being written, such that a put never
+            /// overwrites an existing item
+            pub fn not_exists_condition() -> #condition {
+                #not_exists
+            }
 
-    Ok(quote! {
-        fn from_attrs(mut attrs: #attributes) -> ::std::result::Result<Self, #err> {
-            ::std::result::Result::Ok(Self {
-                #(#field_conversions),*
-            })
+            /// Returns a `ConditionExpression` and its `ExpressionAttributeNames` which hold
+            /// when an item is stored under the key being written, such that a put never
+            /// creates a new item
+            pub fn exists_condition() -> #condition {
+                #exists
+            }
         }
-    })
+    }
 }
 
-fn get_dynomite_item_traits(
-    vis: &Visibility,
+/// ```rust,ignore
+/// impl Name {
+///   pub fn key_attribute_names() -> &'static [&'static str] {
+///     &["partition_key_deser_name", "sort_key_deser_name"]
+///   }
+/// }
+/// ```
+fn get_key_attribute_names_function(
     name: &Ident,
+    generics: &Generics,
+    container: &Container,
     fields: &[ItemField],
 ) -> syn::Result<impl ToTokens> {
-    let impls = get_item_impls(vis, name, fields)?;
-
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names = match key_delegate(fields) {
+        Some(delegate) => {
+            let delegate_ty = &delegate.field.ty;
+            quote!(<#delegate_ty>::key_attribute_names())
+        }
+        None => {
+            let partition_key = fields
+                .iter()
+                .find(|field| field.is_partition_key())
+                .map(ItemField::deser_name);
+            // composite sort key components share a single attribute
+            let sort_key = match CompositeSortKey::new(container, fields)? {
+                Some(composite) => Some(composite.name),
+                None => fields
+                    .iter()
+                    .find(|field| field.is_sort_key())
+                    .map(ItemField::deser_name),
+            };
+            let names = partition_key.into_iter().chain(sort_key);
+            quote!(&[#(#names),*])
+        }
+    };
     Ok(quote! {
-        #impls
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the attribute names of this item's partition key followed by its sort key, if any
+            pub fn key_attribute_names() -> &'static [&'static str] {
+                #names
+            }
+        }
     })
 }
 
-fn get_item_impls(
-    vis: &Visibility,
+/// ```rust,ignore
+/// impl Name {
+///   pub fn schema() -> ::dynomite::Schema {
+///     ::dynomite::Schema {
+///       key_schema: vec![
+///         ::dynomite::schema::KeySchemaElement {
+///           attribute_name: "partition_key_deser_name".into(),
+///           key_type: ::dynomite::schema::KeyType::Hash,
+///         },
+///       ],
+///       attribute_definitions: vec![
+///         ::dynomite::schema::AttributeDefinition {
+///           attribute_name: "partition_key_deser_name".into(),
+///           attribute_type: ::dynomite::schema::ScalarType::S,
+///         },
+///       ],
+///     }
+///   }
+/// }
+/// ```
+fn get_schema_function(
     name: &Ident,
+    generics: &Generics,
+    container: &Container,
     fields: &[ItemField],
 ) -> syn::Result<impl ToTokens> {
-    // impl ::dynomite::Item for Name ...
-    let item_trait = get_item_trait(name, fields)?;
-    // pub struct NameKey ...
-    let key_struct = get_key_struct(vis, name, fields)?;
+    let schema = quote!(::dynomite::schema);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    if let Some(delegate) = key_delegate(fields) {
+        let delegate_ty = &delegate.field.ty;
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns the key schema and key attribute definitions of this item's table
+                pub fn schema() -> ::dynomite::Schema {
+                    <#delegate_ty>::schema()
+                }
+            }
+        });
+    }
+    let partition_key = fields
+        .iter()
+        .find(|field| field.is_partition_key())
+        .map(|field| (field.deser_name(), scalar_type(&field.field.ty)));
+    // composite sort keys are always stored as strings
+    let sort_key = match CompositeSortKey::new(container, fields)? {
+        Some(composite) => Some((composite.name, quote!(S))),
+        None => fields
+            .iter()
+            .find(|field| field.is_sort_key())
+            .map(|field| (field.deser_name(), scalar_type(&field.field.ty))),
+    };
+    let keys = partition_key
+        .map(|key| (key, quote!(Hash)))
+        .into_iter()
+        .chain(sort_key.map(|key| (key, quote!(Range))))
+        .collect::<Vec<_>>();
+    let key_schema = keys.iter().map(|((name, _), key_type)| {
+        quote! {
+            #schema::KeySchemaElement {
+                attribute_name: #name.into(),
+                key_type: #schema::KeyType::#key_type,
+            }
+        }
+    });
+    let attribute_definitions = keys.iter().map(|((name, attribute_type), _)| {
+        quote! {
+            #schema::AttributeDefinition {
+                attribute_name: #name.into(),
+                attribute_type: #schema::ScalarType::#attribute_type,
+            }
+        }
+    });
 
     Ok(quote! {
-        #item_trait
-        #key_struct
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the key schema and key attribute definitions of this item's table
+            pub fn schema() -> ::dynomite::Schema {
+                ::dynomite::Schema {
+                    key_schema: ::std::vec![#(#key_schema),*],
+                    attribute_definitions: ::std::vec![#(#attribute_definitions),*],
+                }
+            }
+        }
     })
 }
 
+/// Infers the scalar type a key field is stored as from its type's name,
+/// numbers are `N`, bytes are `B` and everything else is assumed to be `S`
+fn scalar_type(ty: &Type) -> proc_macro2::TokenStream {
+    if let Type::Array(array) = ty {
+        if is_u8(&array.elem) {
+            return quote!(B);
+        }
+    }
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    };
+    let ident = match segment {
+        Some(segment) => segment.ident.to_string(),
+        None => return quote!(S),
+    };
+    match ident.as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" | "f32" | "f64" => quote!(N),
+        ident if ident.starts_with("NonZero") => quote!(N),
+        "EpochSeconds" | "EpochMillis" | "Decimal" | "BigDecimal" => quote!(N),
+        "Bytes" | "BytesMut" | "BinaryUuid" => quote!(B),
+        "Vec" if is_vec_of_u8(segment) => quote!(B),
+        _ => quote!(S),
+    }
+}
+
+/// Returns true if the path segment is syntactically a `Vec<u8>`
+fn is_vec_of_u8(segment: Option<&syn::PathSegment>) -> bool {
+    match segment.map(|segment| &segment.arguments) {
+        Some(syn::PathArguments::AngleBracketed(args)) => matches!(
+            args.args.first(),
+            Some(syn::GenericArgument::Type(ty)) if is_u8(ty)
+        ),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is syntactically a `u8`
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.is_ident("u8"))
+}
+
+// `String`, however qualified
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map_or(false, |segment| {
+            segment.ident == "String" && segment.arguments.is_empty()
+        }),
+        _ => false,
+    }
+}
+
 /// ```rust,ignore
 /// impl ::dynomite::Item for Name {
 ///   fn key(&self) -> ::std::collections::HashMap<String, ::dynomite::dynamodb::AttributeValue> {
 ///     let mut keys = ::std::collections::HashMap::new();
+///     keys.insert("field_deser_name", to_attribute_value(field.clone()));
+///     keys
+///   }
+///   fn into_key(self) -> ::std::collections::HashMap<String, ::dynomite::dynamodb::AttributeValue> {
+///     let mut keys = ::std::collections::HashMap::new();
 ///     keys.insert("field_deser_name", to_attribute_value(field));
 ///     keys
 ///   }
@@ -491,47 +4629,177 @@ fn get_item_impls(
 /// ```
 fn get_item_trait(
     name: &Ident,
+    generics: &Generics,
+    container: &Container,
     fields: &[ItemField],
 ) -> syn::Result<impl ToTokens> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let item = quote!(::dynomite::Item);
     let attribute_map = quote!(
         ::std::collections::HashMap<String, ::dynomite::dynamodb::AttributeValue>
     );
+    if let Some(delegate) = key_delegate(fields) {
+        let field_ident = &delegate.field.ident;
+        return Ok(quote! {
+            impl #impl_generics #item for #name #ty_generics #where_clause {
+                fn key(&self) -> #attribute_map {
+                    #item::key(&self.#field_ident)
+                }
+
+                fn into_key(self) -> #attribute_map {
+                    #item::into_key(self.#field_ident)
+                }
+            }
+        });
+    }
     let partition_key_field = fields.iter().find(|f| f.is_partition_key());
     let sort_key_field = fields.iter().find(|f| f.is_sort_key());
-    let partition_key_insert = partition_key_field.map(get_key_inserter).transpose()?;
-    let sort_key_insert = sort_key_field.map(get_key_inserter).transpose()?;
+    let partition_key_insert = partition_key_field
+        .map(|field| get_key_inserter(field, false))
+        .transpose()?;
+    let partition_key_move = partition_key_field
+        .map(|field| get_key_inserter(field, true))
+        .transpose()?;
+    let composite = CompositeSortKey::new(container, fields)?;
+    // composite components are only borrowed to be joined
+    let sort_key_move = match composite {
+        Some(_) => None,
+        None => sort_key_field
+            .map(|field| get_key_inserter(field, true))
+            .transpose()?
+            .map(ToTokens::into_token_stream),
+    };
+    let sort_key_insert = match composite {
+        Some(composite) => {
+            let name = &composite.name;
+            let value = composite.join(|field| {
+                let field_ident = &field.field.ident;
+                quote!(self.#field_ident)
+            });
+            Some(quote! {
+                keys.insert(#name.to_string(), #value);
+            })
+        }
+        None => sort_key_field
+            .map(|field| get_key_inserter(field, false))
+            .transpose()?
+            .map(ToTokens::into_token_stream),
+    };
+    let sort_key_move = sort_key_move.or_else(|| sort_key_insert.clone());
 
     Ok(partition_key_field
         .map(|_| {
             quote! {
-                impl #item for #name {
+                impl #impl_generics #item for #name #ty_generics #where_clause {
                     fn key(&self) -> #attribute_map {
                         let mut keys = ::std::collections::HashMap::new();
                         #partition_key_insert
                         #sort_key_insert
                         keys
                     }
+
+                    fn into_key(self) -> #attribute_map {
+                        let mut keys = ::std::collections::HashMap::new();
+                        #sort_key_move
+                        #partition_key_move
+                        keys
+                    }
                 }
             }
         })
         .unwrap_or_else(proc_macro2::TokenStream::new))
 }
 
+/// Returns the flatten field providing an Item's key, if any
+fn key_delegate<'a>(fields: &'a [ItemField<'a>]) -> Option<&'a ItemField<'a>> {
+    fields.iter().find(|field| field.key_delegate().is_some())
+}
+
+/// ```rust,ignore
+/// pub type NameKey = DelegateTypeKey;
+///
+/// impl From<&Name> for DelegateTypeKey {
+///   fn from(item: &Name) -> Self {
+///     From::from(&item.delegate_field)
+///   }
+/// }
+///
+/// impl Name {
+///   pub fn key_struct(&self) -> NameKey {
+///     self.into()
+///   }
+/// }
+/// ```
+fn get_delegated_key_struct(
+    vis: &Visibility,
+    name: &Ident,
+    generics: &Generics,
+    delegate: &ItemField,
+) -> syn::Result<impl ToTokens> {
+    let key_name = Ident::new(&format!("{}Key", name), Span::call_site());
+    // the delegate's own {Type}Key struct, key structs are never generic
+    let mut delegate_key = match &delegate.field.ty {
+        Type::Path(path) if path.qself.is_none() => path.path.clone(),
+        ty => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "key flatten fields must be named types deriving Item",
+            ))
+        }
+    };
+    if let Some(segment) = delegate_key.segments.last_mut() {
+        segment.ident = Ident::new(&format!("{}Key", segment.ident), segment.ident.span());
+        segment.arguments = syn::PathArguments::None;
+    }
+    let field_ident = &delegate.field.ident;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        /// The key of this item, provided by its key flatten field
+        #vis type #key_name = #delegate_key;
+
+        impl #impl_generics ::std::convert::From<&#name #ty_generics> for #delegate_key #where_clause {
+            fn from(item: &#name #ty_generics) -> Self {
+                ::std::convert::From::from(&item.#field_ident)
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns a copy of this item's key fields
+            #[must_use]
+            pub fn key_struct(&self) -> #key_name {
+                self.into()
+            }
+        }
+    })
+}
+
 /// ```rust,ignore
 /// keys.insert(
-///   "field_deser_name", to_attribute_value(field)
+///   "field_deser_name", to_attribute_value(field.clone())
 /// );
 /// ```
-fn get_key_inserter(field: &ItemField) -> syn::Result<impl ToTokens> {
+///
+/// Moves the field rather than cloning it if `owned`
+fn get_key_inserter(
+    field: &ItemField,
+    owned: bool,
+) -> syn::Result<impl ToTokens> {
     let to_attribute_value = quote!(::dynomite::Attribute::into_attr);
 
-    let field_deser_name = field.deser_name();
+    let field_deser_name = field.key_deser_name();
     let field_ident = &field.field.ident;
+    let value = if owned {
+        quote!(self.#field_ident)
+    } else {
+        quote!(self.#field_ident.clone())
+    };
+    let cfgs = field.cfg_attrs();
     Ok(quote! {
+        #(#cfgs)*
         keys.insert(
             #field_deser_name.to_string(),
-            #to_attribute_value(self.#field_ident.clone())
+            #to_attribute_value(#value)
         );
     })
 }
@@ -546,47 +4814,450 @@ fn get_key_inserter(field: &ItemField) -> syn::Result<impl ToTokens> {
 fn get_key_struct(
     vis: &Visibility,
     name: &Ident,
+    generics: &Generics,
+    container: &Container,
     fields: &[ItemField],
 ) -> syn::Result<impl ToTokens> {
+    use syn::spanned::Spanned as _;
+    let item_name = name;
     let name = Ident::new(&format!("{}Key", name), Span::call_site());
 
+    let hash = match container.key_hash() {
+        Some(_) => {
+            // best effort: floating point types are never Hash
+            if let Some(field) = fields.iter().find(|field| {
+                (field.is_partition_key() || field.is_sort_key())
+                    && type_mentions(&field.field.ty, &["f32", "f64"])
+            }) {
+                return Err(syn::Error::new(
+                    field.field.ty.span(),
+                    "key_hash requires key fields to implement `Hash` which floating point types do not",
+                ));
+            }
+            Some(quote!(, Eq, Hash))
+        }
+        None => None,
+    };
+
+    if let Some((lit, _)) = fields
+        .iter()
+        .filter(|field| !(field.is_partition_key() || field.is_sort_key()))
+        .find_map(|field| field.key_as())
+    {
+        return Err(syn::Error::new(
+            lit.span(),
+            "key_as may only be applied to partition_key or sort_key fields",
+        ));
+    }
+
     let partition_key_field = fields
         .iter()
         .find(|field| field.is_partition_key())
-        .cloned()
         .map(|field| {
-            // clone because this is a new struct
-            // note: this in inherits field attrs so that
-            // we retain dynomite(rename = "xxx")
-            let field = field.field.clone();
+            let field = get_key_struct_field(field);
             quote! {
                 #field
             }
         });
 
-    let sort_key_field = fields
+    let sort_key_fields = fields
         .iter()
-        .find(|field| field.is_sort_key())
-        .cloned()
+        .filter(|field| field.is_sort_key())
         .map(|field| {
-            // clone because this is a new struct
-            // note: this in inherits field attrs so that
-            // we retain dynomite(rename = "xxx")
-            let field = field.field.clone();
+            let field = get_key_struct_field(field);
             quote! {
                 #field
             }
         });
 
+    let composite = CompositeSortKey::new(container, fields)?;
+    // the composite sort key attribute is resolved the same way for the key struct
+    let composite_attrs = composite.as_ref().map(|composite| {
+        let name = &composite.name;
+        let separator = &composite.separator;
+        quote!(#[dynomite(sort_key_name = #name, sort_key_separator = #separator)])
+    });
+    // the key struct's own derive refers to the crate by the same path
+    let crate_attr = container
+        .crate_path()
+        .map(|(lit, _)| quote!(#[dynomite(crate = #lit)]));
+    // key fields without a rename of their own resolve their names by the same conventions
+    let rename_all_attr = container
+        .rename_all_serialize()
+        .map(|lit| quote!(#[dynomite(rename_all_serialize = #lit)]));
+    let rename_all_deserialize_attr = container
+        .rename_all_deserialize()
+        .map(|lit| quote!(#[dynomite(rename_all_deserialize = #lit)]));
+    let query_condition = get_query_condition_function(composite.as_ref(), fields);
+    let key_string = get_key_string_functions(container, composite.as_ref(), fields);
+    let key_struct_conversion = get_key_struct_conversion(item_name, generics, &name, fields);
+
     Ok(partition_key_field
         .map(|partition_key_field| {
             quote! {
-                #[derive(::dynomite::Attributes, Debug, Clone, PartialEq)]
+                #[derive(::dynomite::Attributes, Debug, Clone, PartialEq #hash)]
+                #composite_attrs
+                #crate_attr
+                #rename_all_attr
+                #rename_all_deserialize_attr
                 #vis struct #name {
                     #partition_key_field,
-                    #sort_key_field
+                    #(#sort_key_fields),*
+                }
+
+                impl #name {
+                    #query_condition
+                    #key_string
                 }
+
+                #key_struct_conversion
             }
         })
         .unwrap_or_else(proc_macro2::TokenStream::new))
 }
+
+/// ```rust,ignore
+/// impl From<&Name> for NameKey {
+///   fn from(item: &Name) -> Self {
+///     Self {
+///       partition_key_field: item.partition_key_field.clone(),
+///       sort_key_field: item.sort_key_field.clone(),
+///     }
+///   }
+/// }
+///
+/// impl Name {
+///   pub fn key_struct(&self) -> NameKey {
+///     self.into()
+///   }
+/// }
+/// ```
+// the declaration of a key field within the {Name}Key struct
+fn get_key_struct_field(field: &ItemField) -> Field {
+    // clone because this is a new struct
+    // note: this in inherits field attrs so that
+    // we retain dynomite(rename = "xxx") and dynomite(order = N)
+    let mut key_field = field.field.clone();
+    // foreign attributes belong to derives the key struct does not share
+    key_field.attrs.retain(|attr| {
+        attr.path.is_ident("dynomite") || attr.path.is_ident("doc") || attr.path.is_ident("cfg")
+    });
+    if let Some((_, ty)) = field.key_as() {
+        key_field.ty = ty;
+    }
+    if let Some((_, key_rename)) = field.key_rename() {
+        key_field.attrs = key_field
+            .attrs
+            .into_iter()
+            .map(|attr| rename_key_attr(attr, key_rename))
+            .collect();
+    }
+    key_field
+}
+
+// replaces the item's `rename` of a key field with its `key_rename`
+//
+// #[dynomite(partition_key, rename = "x", key_rename = "y")] => #[dynomite(partition_key, rename = "y")]
+fn rename_key_attr(
+    attr: Attribute,
+    key_rename: &LitStr,
+) -> Attribute {
+    use syn::{Meta, NestedMeta};
+    if !attr.path.is_ident("dynomite") {
+        return attr;
+    }
+    let nested = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list.nested,
+        _ => return attr,
+    };
+    let is_named = |nested: &NestedMeta, name: &str| match nested {
+        NestedMeta::Meta(Meta::NameValue(name_value)) => name_value.path.is_ident(name),
+        _ => false,
+    };
+    let rename = if nested.iter().any(|nested| is_named(nested, "key_rename")) {
+        Some(quote!(rename = #key_rename))
+    } else {
+        None
+    };
+    let args = nested
+        .iter()
+        .filter(|nested| !(is_named(nested, "rename") || is_named(nested, "key_rename")))
+        .map(ToTokens::to_token_stream)
+        .chain(rename)
+        .collect::<Vec<_>>();
+    syn::parse_quote!(#[dynomite(#(#args),*)])
+}
+
+fn get_key_struct_conversion(
+    item_name: &Ident,
+    generics: &Generics,
+    key_name: &impl ToTokens,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let key_fields = fields
+        .iter()
+        .filter(|field| field.is_partition_key() || field.is_sort_key())
+        .map(|field| {
+            let cfgs = field.cfg_attrs();
+            let field_ident = &field.field.ident;
+            let value = quote!(::std::clone::Clone::clone(&item.#field_ident));
+            let init = match field.key_as() {
+                Some((lit, key_ty)) => {
+                    let field_ty = &field.field.ty;
+                    quote_spanned! {lit.span()=>
+                        #field_ident: <#key_ty as ::std::convert::From<#field_ty>>::from(#value)
+                    }
+                }
+                None => quote!(#field_ident: #value),
+            };
+            quote!(#(#cfgs)* #init)
+        });
+
+    quote! {
+        impl #impl_generics ::std::convert::From<&#item_name #ty_generics> for #key_name #where_clause {
+            fn from(item: &#item_name #ty_generics) -> Self {
+                Self {
+                    #(#key_fields),*
+                }
+            }
+        }
+
+        impl #impl_generics #item_name #ty_generics #where_clause {
+            /// Returns a copy of this item's key fields
+            #[must_use]
+            pub fn key_struct(&self) -> #key_name {
+                self.into()
+            }
+        }
+    }
+}
+
+/// The partition key field followed by each sort key field, in composite `order`
+fn key_string_fields<'a>(
+    composite: Option<&CompositeSortKey<'a>>,
+    fields: &'a [ItemField<'a>],
+) -> Vec<&'a ItemField<'a>> {
+    let sort_keys = match composite {
+        Some(composite) => composite.fields.clone(),
+        None => fields.iter().filter(|field| field.is_sort_key()).collect(),
+    };
+    fields
+        .iter()
+        .filter(|field| field.is_partition_key())
+        .chain(sort_keys)
+        .collect()
+}
+
+/// ```rust,ignore
+/// impl Name {
+///   pub fn key_string(&self) -> String {
+///     ::dynomite::composite::key_string(&[self.partition_key_field.to_string(), ..], "#")
+///   }
+/// }
+/// ```
+fn get_item_key_string_function(
+    name: &Ident,
+    generics: &Generics,
+    container: &Container,
+    fields: &[ItemField],
+) -> syn::Result<impl ToTokens> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let doc = quote! {
+        /// Returns the values of this item's key fields joined into a single string
+    };
+    if let Some(delegate) = key_delegate(fields) {
+        let field_ident = &delegate.field.ident;
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #doc
+                pub fn key_string(&self) -> ::std::string::String {
+                    self.#field_ident.key_string()
+                }
+            }
+        });
+    }
+    let composite = CompositeSortKey::new(container, fields)?;
+    let key_fields = key_string_fields(composite.as_ref(), fields);
+    if key_fields.is_empty() {
+        return Ok(proc_macro2::TokenStream::new());
+    }
+    let join = get_key_string_join(&container.key_delimiter(), &key_fields, |field| {
+        field.field.ty.clone()
+    });
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #doc
+            #join
+        }
+    })
+}
+
+// pub fn key_string(&self) -> String where for<'__dynomite> FieldType: ToString {
+//   ::dynomite::composite::key_string(&[self.partition_key_field.to_string(), ..], "#")
+// }
+fn get_key_string_join(
+    delimiter: &str,
+    key_fields: &[&ItemField],
+    ty: impl Fn(&ItemField) -> Type,
+) -> proc_macro2::TokenStream {
+    let values = key_fields.iter().map(|field| {
+        let field_ident = &field.field.ident;
+        quote!(::std::string::ToString::to_string(&self.#field_ident))
+    });
+    // higher-ranked bounds defer checking until the method is used
+    let bounds = key_fields.iter().map(|field| {
+        let field_ty = ty(field);
+        quote!(for<'__dynomite> #field_ty: ::std::string::ToString)
+    });
+    quote! {
+        pub fn key_string(&self) -> ::std::string::String
+        where
+            #(#bounds),*
+        {
+            ::dynomite::composite::key_string(&[#(#values),*], #delimiter)
+        }
+    }
+}
+
+// pub fn key_string(&self) -> String { .. }
+//
+// pub fn parse_key_string(s: &str) -> Result<Self, ::dynomite::AttributeError> {
+//   let components = ::dynomite::composite::parse_key_string(s, "#", 2)?;
+//   Ok(Self {
+//     partition_key_field: components[0].parse().map_err(|_| ::dynomite::AttributeError::InvalidFormat)?,
+//     ..
+//   })
+// }
+fn get_key_string_functions(
+    container: &Container,
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let delimiter = container.key_delimiter();
+    let key_fields = key_string_fields(composite, fields);
+    let key_ty = |field: &ItemField| get_key_struct_field(field).ty;
+    let join = get_key_string_join(&delimiter, &key_fields, key_ty);
+    let count = key_fields.len();
+    let bounds = key_fields.iter().map(|field| {
+        let field_ty = key_ty(field);
+        quote!(for<'__dynomite> #field_ty: ::std::str::FromStr)
+    });
+    let components = key_fields.iter().enumerate().map(|(index, field)| {
+        let field_ident = &field.field.ident;
+        quote! {
+            #field_ident: components[#index]
+                .parse()
+                .map_err(|_| ::dynomite::AttributeError::InvalidFormat)?
+        }
+    });
+
+    quote! {
+        /// Returns the values of this key's fields joined into a single string
+        #join
+
+        /// Parses a key from the string returned by `key_string`
+        pub fn parse_key_string(
+            s: &str
+        ) -> ::std::result::Result<Self, ::dynomite::AttributeError>
+        where
+            #(#bounds),*
+        {
+            let components = ::dynomite::composite::parse_key_string(s, #delimiter, #count)?;
+            ::std::result::Result::Ok(Self {
+                #(#components),*
+            })
+        }
+    }
+}
+
+/// ```rust,ignore
+/// /// Returns a `KeyConditionExpression`, its names and its values for an equality query on this key
+/// pub fn query_condition(&self) -> (String, HashMap<String, String>, ::dynomite::Attributes) {
+///   let mut names = HashMap::new();
+///   names.insert("#pk".to_string(), "partition_key_deser_name".to_string());
+///   names.insert("#sk".to_string(), "sort_key_deser_name".to_string());
+///   let mut values = ::dynomite::Attributes::new();
+///   values.insert(":pk".to_string(), to_attribute_value(self.partition_key_field.clone()));
+///   values.insert(":sk".to_string(), to_attribute_value(self.sort_key_field.clone()));
+///   ("#pk = :pk AND #sk = :sk".to_string(), names, values)
+/// }
+/// ```
+fn get_query_condition_function(
+    composite: Option<&CompositeSortKey>,
+    fields: &[ItemField],
+) -> impl ToTokens {
+    let to_attribute_value = quote!(::dynomite::Attribute::into_attr);
+    let attributes = quote!(::dynomite::Attributes);
+
+    // (attribute name, placeholder, value), the name being substituted for `#placeholder`
+    // and the value for `:placeholder`
+    let mut key_values = fields
+        .iter()
+        .filter(|field| field.is_partition_key())
+        .map(|field| {
+            let field_ident = &field.field.ident;
+            (
+                field.deser_name(),
+                "pk",
+                quote!(#to_attribute_value(self.#field_ident.clone())),
+            )
+        })
+        .collect::<Vec<_>>();
+    match composite {
+        Some(composite) => key_values.push((
+            composite.name.clone(),
+            "sk",
+            composite.join(|field| {
+                let field_ident = &field.field.ident;
+                quote!(self.#field_ident)
+            }),
+        )),
+        None => key_values.extend(
+            fields
+                .iter()
+                .find(|field| field.is_sort_key())
+                .map(|field| {
+                    let field_ident = &field.field.ident;
+                    (
+                        field.deser_name(),
+                        "sk",
+                        quote!(#to_attribute_value(self.#field_ident.clone())),
+                    )
+                }),
+        ),
+    }
+
+    let expression = key_values
+        .iter()
+        .map(|(_, placeholder, _)| format!("#{0} = :{0}", placeholder))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let inserts = key_values.iter().map(|(name, placeholder, value)| {
+        let name_placeholder = format!("#{}", placeholder);
+        let value_placeholder = format!(":{}", placeholder);
+        quote! {
+            names.insert(#name_placeholder.to_string(), #name.to_string());
+            values.insert(
+                #value_placeholder.to_string(),
+                #value
+            );
+        }
+    });
+    let string = quote!(::std::string::String);
+
+    quote! {
+        /// Returns a `KeyConditionExpression` along with its `ExpressionAttributeNames` and
+        /// `ExpressionAttributeValues` for an equality query on this key
+        #[must_use]
+        pub fn query_condition(
+            &self
+        ) -> (#string, ::std::collections::HashMap<#string, #string>, #attributes) {
+            let mut names = ::std::collections::HashMap::new();
+            let mut values = #attributes::new();
+            #(#inserts)*
+            (#expression.to_string(), names, values)
+        }
+    }
+}
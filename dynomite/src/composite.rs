@@ -0,0 +1,185 @@
+//! Support for composite sort keys
+//!
+//! Single table designs often encode a hierarchy in their sort keys, such as `ORDER#123`.
+//! Fields marked with `#[dynomite(sort_key, order = N)]` are joined in order into a single
+//! `S` attribute using [into_attr](fn.into_attr.html) and split back into their components
+//! with [from_attr](fn.from_attr.html).
+//!
+//! Components are split from the left, so only the last component may itself
+//! contain the separator.
+//!
+//! [key_string](fn.key_string.html) and [parse_key_string](fn.parse_key_string.html)
+//! instead join key values into a textual form of an item's key, such as `people#123`,
+//! escaping each `\` and each character a delimiter starts with within a value with a
+//! preceding `\` so that any value may contain the delimiter.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::composite;
+//!
+//! let value = composite::into_attr(&["ORDER".to_string(), "123".to_string()], "#");
+//! assert_eq!(value.s, Some("ORDER#123".into()));
+//! assert_eq!(composite::from_attr::<u32>(&value, "#", 2, 1), Ok(123));
+//! ```
+use crate::{AttributeError, AttributeValue};
+use std::str::FromStr;
+
+/// Returns an `S` attribute value joining each component with a separator
+pub fn into_attr(
+    components: &[String],
+    separator: &str,
+) -> AttributeValue {
+    AttributeValue {
+        s: Some(components.join(separator)),
+        ..AttributeValue::default()
+    }
+}
+
+/// Parses the component at `index` of an `S` attribute value holding `count` components
+pub fn from_attr<T: FromStr>(
+    value: &AttributeValue,
+    separator: &str,
+    count: usize,
+    index: usize,
+) -> Result<T, AttributeError> {
+    let s = value.s.as_ref().ok_or(AttributeError::InvalidType)?;
+    let components = s.splitn(count, separator).collect::<Vec<_>>();
+    if components.len() != count {
+        return Err(AttributeError::InvalidFormat);
+    }
+    components
+        .get(index)
+        .ok_or(AttributeError::InvalidFormat)?
+        .parse()
+        .map_err(|_| AttributeError::InvalidFormat)
+}
+
+/// Joins key values with a delimiter, escaping each `\` or first character of the
+/// delimiter within a value with a preceding `\`
+pub fn key_string(
+    values: &[String],
+    delimiter: &str,
+) -> String {
+    values
+        .iter()
+        .map(|value| {
+            let mut escaped = String::with_capacity(value.len());
+            for c in value.chars() {
+                // unescaped, these only ever start a delimiter
+                if c == '\\' || delimiter.starts_with(c) {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        })
+        .collect::<Vec<_>>()
+        .join(delimiter)
+}
+
+/// Splits a string joined by [key_string](fn.key_string.html) into its `count` values
+pub fn parse_key_string(
+    s: &str,
+    delimiter: &str,
+    count: usize,
+) -> Result<Vec<String>, AttributeError> {
+    let mut values = Vec::with_capacity(count);
+    let mut value = String::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            // an escaped character is taken as is
+            let escaped = rest[1..]
+                .chars()
+                .next()
+                .ok_or(AttributeError::InvalidFormat)?;
+            value.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else if rest.starts_with(delimiter) {
+            values.push(std::mem::take(&mut value));
+            rest = &rest[delimiter.len()..];
+        } else {
+            value.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    values.push(value);
+    if values.len() != count {
+        return Err(AttributeError::InvalidFormat);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_strings_round_trip() {
+        for values in &[
+            vec!["people".to_string(), "123".to_string()],
+            vec!["a#b".to_string(), "c\\#".to_string()],
+            vec!["".to_string(), "\\".to_string()],
+        ] {
+            let s = key_string(values, "#");
+            assert_eq!(parse_key_string(&s, "#", values.len()).as_ref(), Ok(values));
+        }
+        assert_eq!(
+            key_string(&["a#b".to_string(), "c\\".to_string()], "#"),
+            "a\\#b#c\\\\"
+        );
+    }
+
+    #[test]
+    fn key_strings_support_multi_character_delimiters() {
+        let values = vec!["a/".to_string(), "//b".to_string()];
+        let s = key_string(&values, "//");
+        assert_eq!(s, "a\\///\\/\\/b");
+        assert_eq!(parse_key_string(&s, "//", 2), Ok(values));
+    }
+
+    #[test]
+    fn rejects_malformed_key_strings() {
+        assert_eq!(
+            parse_key_string("a#b#c", "#", 2),
+            Err(AttributeError::InvalidFormat)
+        );
+        assert_eq!(
+            parse_key_string("a#b\\", "#", 2),
+            Err(AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn last_component_may_contain_separator() {
+        let value = into_attr(&["a".to_string(), "b#c".to_string()], "#");
+        assert_eq!(from_attr::<String>(&value, "#", 2, 0), Ok("a".to_string()));
+        assert_eq!(
+            from_attr::<String>(&value, "#", 2, 1),
+            Ok("b#c".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_missing_components() {
+        let value = into_attr(&["a".to_string()], "#");
+        assert_eq!(
+            from_attr::<String>(&value, "#", 2, 1),
+            Err(AttributeError::InvalidFormat)
+        );
+        assert_eq!(
+            from_attr::<String>(&AttributeValue::default(), "#", 2, 1),
+            Err(AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    fn rejects_unparsable_components() {
+        let value = into_attr(&["a".to_string(), "b".to_string()], "#");
+        assert_eq!(
+            from_attr::<u32>(&value, "#", 2, 1),
+            Err(AttributeError::InvalidFormat)
+        );
+    }
+}
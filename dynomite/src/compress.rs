@@ -0,0 +1,101 @@
+//! Support for compressing large attribute values
+//!
+//! Fields marked with `#[dynomite(compress = "gzip")]` are stored as gzip
+//! compressed `B` attribute values using [gzip::into_attr](gzip/fn.into_attr.html)
+//! and [gzip::from_attr](gzip/fn.from_attr.html). `S` and `B` values are compressed,
+//! other attribute types are stored as is.
+//!
+//! Stored values which are not gzip compressed are passed through to the field's
+//! `Attribute` impl unchanged so that existing data remains readable while it is
+//! migrated.
+
+/// gzip compression, enabled by the `gzip` feature
+pub mod gzip {
+    use crate::{Attribute, AttributeError, AttributeValue};
+    use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+    use std::io::{Read, Write};
+
+    // https://tools.ietf.org/html/rfc1952#page-6
+    const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+    /// Returns a gzip compressed `B` attribute value of a value's `S` or `B` representation
+    pub fn into_attr<T: Attribute>(value: T) -> AttributeValue {
+        let value = value.into_attr();
+        let bytes = match (value.s, value.b) {
+            (Some(s), _) => s.into_bytes(),
+            (_, Some(b)) => b.to_vec(),
+            (s, b) => return AttributeValue { s, b, ..value },
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        let compressed = encoder
+            .write_all(&bytes)
+            .and_then(|_| encoder.finish())
+            .expect("writes to a Vec are infallible");
+        AttributeValue {
+            b: Some(compressed.into()),
+            ..AttributeValue::default()
+        }
+    }
+
+    /// Parses a gzip compressed `B` attribute value, passing through values which are not compressed
+    pub fn from_attr<T: Attribute>(value: AttributeValue) -> Result<T, AttributeError> {
+        let compressed = match &value.b {
+            Some(b) if b.starts_with(&MAGIC) => b.clone(),
+            _ => return T::from_attr(value),
+        };
+        let mut bytes = Vec::new();
+        GzDecoder::new(&compressed[..])
+            .read_to_end(&mut bytes)
+            .map_err(|_| AttributeError::InvalidFormat)?;
+        // the compressed value may have been either a `B` or an `S`
+        match T::from_attr(AttributeValue {
+            b: Some(bytes.clone().into()),
+            ..AttributeValue::default()
+        }) {
+            Err(AttributeError::InvalidType) => T::from_attr(AttributeValue {
+                s: Some(String::from_utf8(bytes).map_err(|_| AttributeError::InvalidFormat)?),
+                ..AttributeValue::default()
+            }),
+            result => result,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn compresses_strings() {
+            let value = "hello ".repeat(100);
+            let attr = into_attr(value.clone());
+            assert!(attr.s.is_none());
+            let compressed = attr.b.clone().expect("expected binary");
+            assert!(compressed.starts_with(&MAGIC));
+            assert!(compressed.len() < value.len());
+            assert_eq!(from_attr::<String>(attr), Ok(value));
+        }
+
+        #[test]
+        fn compresses_bytes() {
+            let value = vec![1_u8; 100];
+            assert_eq!(from_attr::<Vec<u8>>(into_attr(value.clone())), Ok(value));
+        }
+
+        #[test]
+        fn passes_through_uncompressed_values() {
+            assert_eq!(
+                from_attr::<String>("legacy".to_string().into_attr()),
+                Ok("legacy".to_string())
+            );
+            assert_eq!(
+                from_attr::<Vec<u8>>(vec![1_u8, 2, 3].into_attr()),
+                Ok(vec![1_u8, 2, 3])
+            );
+        }
+
+        #[test]
+        fn stores_other_types_as_is() {
+            assert_eq!(into_attr(1_u32), 1_u32.into_attr());
+        }
+    }
+}
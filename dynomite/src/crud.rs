@@ -0,0 +1,104 @@
+//! Support for generated CRUD methods
+//!
+//! Items deriving `Item` with the `#[dynomite(crud)]` and `#[dynomite(table = "...")]`
+//! container attributes provide async `get`, `save` and `delete` methods which
+//! fail with a [CrudError](enum.CrudError.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::{dynamodb::DynamoDb, Item};
+//!
+//! #[derive(Item, Clone)]
+//! #[dynomite(crud, table = "people")]
+//! struct Person {
+//!     #[dynomite(partition_key)]
+//!     id: String,
+//!     name: String,
+//! }
+//!
+//! async fn rename<C: DynamoDb + ?Sized>(
+//!     client: &C,
+//!     id: String,
+//! ) -> Result<(), Box<dyn std::error::Error>> {
+//!     if let Some(mut person) = Person::get(client, PersonKey { id }).await? {
+//!         person.name = "Jane".into();
+//!         person.save(client).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+use crate::AttributeError;
+#[cfg(feature = "default")]
+use rusoto_core_default::RusotoError;
+#[cfg(feature = "rustls")]
+use rusoto_core_rustls::RusotoError;
+use std::{error::Error, fmt};
+
+/// Errors that may result of generated CRUD methods
+#[derive(Debug, PartialEq)]
+pub enum CrudError<E> {
+    /// Will be returned if the underlying DynamoDB request fails
+    Rusoto(RusotoError<E>),
+    /// Will be returned if the attributes of a response could not be converted into an item
+    Attribute(AttributeError),
+}
+
+impl<E> From<RusotoError<E>> for CrudError<E> {
+    fn from(err: RusotoError<E>) -> Self {
+        CrudError::Rusoto(err)
+    }
+}
+
+impl<E> From<AttributeError> for CrudError<E> {
+    fn from(err: AttributeError) -> Self {
+        CrudError::Attribute(err)
+    }
+}
+
+impl<E: Error + 'static> fmt::Display for CrudError<E> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            CrudError::Rusoto(err) => write!(f, "Request failed: {}", err),
+            CrudError::Attribute(err) => write!(f, "Invalid item: {}", err),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for CrudError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CrudError::Rusoto(err) => Some(err),
+            CrudError::Attribute(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamodb::GetItemError;
+
+    #[test]
+    fn attribute_errors_display() {
+        assert_eq!(
+            "Invalid item: Invalid type",
+            format!(
+                "{}",
+                CrudError::<GetItemError>::from(AttributeError::InvalidType)
+            )
+        )
+    }
+
+    #[test]
+    fn rusoto_errors_display() {
+        let err = CrudError::from(RusotoError::Service(GetItemError::InternalServerError(
+            "boom".into(),
+        )));
+        assert_eq!("Request failed: boom", format!("{}", err));
+        assert!(err.source().is_some());
+    }
+}
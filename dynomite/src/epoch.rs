@@ -0,0 +1,488 @@
+//! Support for attributes holding fractional epoch seconds
+//!
+//! Timestamps implement `Attribute` as rfc3339 formatted `S` values. Fields marked
+//! with `#[dynomite(epoch)]` are instead stored as `N` values holding seconds since
+//! the Unix epoch using [into_attr](fn.into_attr.html) and [from_attr](fn.from_attr.html).
+//! Unlike [ttl](../ttl/index.html) attributes, these retain sub-second precision,
+//! down to nanoseconds, as well as times before the epoch.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::epoch;
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! let created = UNIX_EPOCH + Duration::from_millis(1_500);
+//! let value = epoch::into_attr(created);
+//! assert_eq!(value.n, Some("1.5".into()));
+//! assert_eq!(epoch::from_attr::<SystemTime>(value), Ok(created));
+//! ```
+//!
+//! Fields which should always be stored as whole numbers, such as sort keys queried by
+//! numeric ranges, may instead be wrapped in [EpochSeconds](struct.EpochSeconds.html)
+//! or [EpochMillis](struct.EpochMillis.html). These implement `Attribute` for any
+//! timestamp type convertible to and from `SystemTime`, including chrono's `DateTime<Utc>`
+//! and the time crate's `OffsetDateTime`, truncating their precision on construction.
+//!
+//! ```
+//! use dynomite::{epoch::EpochMillis, Attribute};
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! let created = EpochMillis::new(UNIX_EPOCH + Duration::from_micros(1_500_999))?;
+//! assert_eq!(*created, UNIX_EPOCH + Duration::from_millis(1_500));
+//! let value = created.into_attr();
+//! assert_eq!(value.n, Some("1500".into()));
+//! assert_eq!(EpochMillis::<SystemTime>::from_attr(value), Ok(created));
+//! # Ok::<(), dynomite::AttributeError>(())
+//! ```
+use crate::{Attribute, AttributeError, AttributeValue};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use std::{
+    ops::Deref,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A type which may be stored as fractional epoch seconds
+pub trait Epoch: Sized {
+    /// Returns an `N` attribute value holding fractional epoch seconds
+    fn into_epoch(self) -> AttributeValue;
+
+    /// Parses an `N` attribute value holding integer or fractional epoch seconds
+    fn from_epoch(value: AttributeValue) -> Result<Self, AttributeError>;
+}
+
+impl Epoch for SystemTime {
+    fn into_epoch(self) -> AttributeValue {
+        let n = match self.duration_since(UNIX_EPOCH) {
+            Ok(since) => format_seconds(false, since),
+            Err(before) => format_seconds(true, before.duration()),
+        };
+        AttributeValue {
+            n: Some(n),
+            ..AttributeValue::default()
+        }
+    }
+
+    fn from_epoch(value: AttributeValue) -> Result<Self, AttributeError> {
+        let n = value.n.ok_or(AttributeError::InvalidType)?;
+        let (negative, duration) = parse_seconds(&n)?;
+        if negative {
+            UNIX_EPOCH.checked_sub(duration)
+        } else {
+            UNIX_EPOCH.checked_add(duration)
+        }
+        .ok_or(AttributeError::InvalidFormat)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Epoch for DateTime<Utc> {
+    fn into_epoch(self) -> AttributeValue {
+        SystemTime::from(self).into_epoch()
+    }
+
+    fn from_epoch(value: AttributeValue) -> Result<Self, AttributeError> {
+        SystemTime::from_epoch(value).map(Into::into)
+    }
+}
+
+/// `None` is represented by the `NULL` AttributeValue type
+impl<T: Epoch> Epoch for Option<T> {
+    fn into_epoch(self) -> AttributeValue {
+        match self {
+            Some(value) => value.into_epoch(),
+            _ => AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+        }
+    }
+
+    fn from_epoch(value: AttributeValue) -> Result<Self, AttributeError> {
+        match value.null {
+            Some(true) => Ok(None),
+            _ => Ok(Some(T::from_epoch(value)?)),
+        }
+    }
+}
+
+/// Converts a value into an `N` attribute value holding fractional epoch seconds
+pub fn into_attr<T: Epoch>(value: T) -> AttributeValue {
+    value.into_epoch()
+}
+
+/// Parses an `N` attribute value holding integer or fractional epoch seconds
+pub fn from_attr<T: Epoch>(value: AttributeValue) -> Result<T, AttributeError> {
+    T::from_epoch(value)
+}
+
+/// A timestamp stored as an `N` attribute value holding whole seconds since the Unix epoch
+///
+/// Sub-second precision is truncated on construction. Times before the epoch or after
+/// the year 9999 are rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochSeconds<T> {
+    value: T,
+    seconds: u64,
+}
+
+impl<T: Into<SystemTime> + From<SystemTime>> EpochSeconds<T> {
+    /// Returns `value` truncated to whole seconds
+    pub fn new(value: T) -> Result<Self, AttributeError> {
+        let seconds = units_since_epoch(value.into(), 1)?;
+        Ok(Self {
+            value: T::from(from_units_since_epoch(seconds, 1)?),
+            seconds,
+        })
+    }
+}
+
+impl<T> EpochSeconds<T> {
+    /// Returns the whole seconds since the Unix epoch
+    pub fn as_secs(&self) -> u64 {
+        self.seconds
+    }
+
+    /// Returns the wrapped timestamp
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for EpochSeconds<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: From<SystemTime>> Attribute for EpochSeconds<T> {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            n: Some(self.seconds.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        let seconds = parse_units(value)?;
+        Ok(Self {
+            value: T::from(from_units_since_epoch(seconds, 1)?),
+            seconds,
+        })
+    }
+}
+
+/// A timestamp stored as an `N` attribute value holding whole milliseconds since the Unix epoch
+///
+/// Sub-millisecond precision is truncated on construction. Times before the epoch or after
+/// the year 9999 are rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpochMillis<T> {
+    value: T,
+    millis: u64,
+}
+
+impl<T: Into<SystemTime> + From<SystemTime>> EpochMillis<T> {
+    /// Returns `value` truncated to whole milliseconds
+    pub fn new(value: T) -> Result<Self, AttributeError> {
+        let millis = units_since_epoch(value.into(), MILLIS_PER_SECOND)?;
+        Ok(Self {
+            value: T::from(from_units_since_epoch(millis, MILLIS_PER_SECOND)?),
+            millis,
+        })
+    }
+}
+
+impl<T> EpochMillis<T> {
+    /// Returns the whole milliseconds since the Unix epoch
+    pub fn as_millis(&self) -> u64 {
+        self.millis
+    }
+
+    /// Returns the wrapped timestamp
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for EpochMillis<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: From<SystemTime>> Attribute for EpochMillis<T> {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            n: Some(self.millis.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        let millis = parse_units(value)?;
+        Ok(Self {
+            value: T::from(from_units_since_epoch(millis, MILLIS_PER_SECOND)?),
+            millis,
+        })
+    }
+}
+
+const MILLIS_PER_SECOND: u64 = 1_000;
+
+// 9999-12-31T23:59:59Z, the last second chrono and time convert from a `SystemTime`
+// without panicking
+const MAX_SECONDS: u64 = 253_402_300_799;
+
+/// Returns the whole units, of which there are `units_per_second`, from the epoch to `time`
+fn units_since_epoch(
+    time: SystemTime,
+    units_per_second: u64,
+) -> Result<u64, AttributeError> {
+    let since = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| AttributeError::InvalidValue {
+            message: "times before the Unix epoch may not be stored as whole epoch units".into(),
+        })?;
+    if since.as_secs() > MAX_SECONDS {
+        return Err(AttributeError::InvalidValue {
+            message: "times after the year 9999 may not be stored as whole epoch units".into(),
+        });
+    }
+    let nanos_per_unit = 1_000_000_000 / units_per_second;
+    Ok(since.as_secs() * units_per_second + u64::from(since.subsec_nanos()) / nanos_per_unit)
+}
+
+/// Returns the time `units`, of which there are `units_per_second`, after the epoch
+fn from_units_since_epoch(
+    units: u64,
+    units_per_second: u64,
+) -> Result<SystemTime, AttributeError> {
+    let seconds = units / units_per_second;
+    if seconds > MAX_SECONDS {
+        return Err(AttributeError::InvalidFormat);
+    }
+    let nanos = (units % units_per_second) * (1_000_000_000 / units_per_second);
+    UNIX_EPOCH
+        .checked_add(Duration::new(seconds, nanos as u32))
+        .ok_or(AttributeError::InvalidFormat)
+}
+
+/// Parses an `N` attribute value holding a non-negative integer
+fn parse_units(value: AttributeValue) -> Result<u64, AttributeError> {
+    value
+        .n
+        .ok_or(AttributeError::InvalidType)?
+        .parse::<u64>()
+        .map_err(|_| AttributeError::InvalidFormat)
+}
+
+/// Formats a duration from the epoch as seconds, omitting trailing zeros of the fraction
+fn format_seconds(
+    negative: bool,
+    duration: Duration,
+) -> String {
+    let sign = if negative && duration != Duration::default() {
+        "-"
+    } else {
+        ""
+    };
+    match duration.subsec_nanos() {
+        0 => format!("{}{}", sign, duration.as_secs()),
+        nanos => format!(
+            "{}{}.{}",
+            sign,
+            duration.as_secs(),
+            format!("{:09}", nanos).trim_end_matches('0')
+        ),
+    }
+}
+
+/// Parses decimal seconds without a loss of precision. Digits finer than
+/// nanoseconds are truncated
+fn parse_seconds(n: &str) -> Result<(bool, Duration), AttributeError> {
+    let (negative, digits) = match n.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, n),
+    };
+    let (whole, fraction) = match digits.find('.') {
+        Some(index) => (&digits[..index], &digits[index + 1..]),
+        None => (digits, ""),
+    };
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(AttributeError::InvalidFormat);
+    }
+    let seconds = match whole {
+        "" => 0,
+        whole => whole
+            .parse::<u64>()
+            .map_err(|_| AttributeError::InvalidFormat)?,
+    };
+    let nanos = fraction
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(9)
+        .collect::<String>()
+        .parse::<u32>()
+        .map_err(|_| AttributeError::InvalidFormat)?;
+    Ok((negative, Duration::new(seconds, nanos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(value: &str) -> AttributeValue {
+        AttributeValue {
+            n: Some(value.into()),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn writes_whole_seconds_without_fraction() {
+        assert_eq!(
+            into_attr(UNIX_EPOCH + Duration::from_secs(10)).n,
+            Some("10".into())
+        );
+    }
+
+    #[test]
+    fn retains_sub_second_precision() {
+        let time = UNIX_EPOCH + Duration::new(10, 123_456_789);
+        let value = into_attr(time);
+        assert_eq!(value.n, Some("10.123456789".into()));
+        assert_eq!(from_attr::<SystemTime>(value), Ok(time));
+        assert_eq!(
+            from_attr::<SystemTime>(n("10.5")),
+            Ok(UNIX_EPOCH + Duration::from_millis(10_500))
+        );
+    }
+
+    #[test]
+    fn retains_pre_epoch_values() {
+        let time = UNIX_EPOCH - Duration::from_millis(1_250);
+        let value = into_attr(time);
+        assert_eq!(value.n, Some("-1.25".into()));
+        assert_eq!(from_attr::<SystemTime>(value), Ok(time));
+    }
+
+    #[test]
+    fn truncates_digits_finer_than_nanoseconds() {
+        assert_eq!(
+            from_attr::<SystemTime>(n("1.0000000019")),
+            Ok(UNIX_EPOCH + Duration::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn rejects_non_numbers() {
+        for invalid in &["soon", "", ".", "1.2.3", "1e3", "--1"] {
+            assert_eq!(
+                from_attr::<SystemTime>(n(invalid)),
+                Err(AttributeError::InvalidFormat)
+            );
+        }
+        assert_eq!(
+            from_attr::<SystemTime>(AttributeValue::default()),
+            Err(AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_round_trips() {
+        use chrono::offset::TimeZone;
+        let time = Utc.ymd(2020, 9, 13).and_hms_milli(12, 26, 40, 5);
+        let value = into_attr(time);
+        assert_eq!(value.n, Some("1600000000.005".into()));
+        assert_eq!(from_attr::<DateTime<Utc>>(value), Ok(time));
+    }
+
+    #[test]
+    fn epoch_seconds_truncate_and_round_trip() {
+        let time =
+            EpochSeconds::new(UNIX_EPOCH + Duration::new(10, 999_999_999)).expect("valid time");
+        assert_eq!(*time, UNIX_EPOCH + Duration::from_secs(10));
+        assert_eq!(time.as_secs(), 10);
+        let value = time.into_attr();
+        assert_eq!(value.n, Some("10".into()));
+        assert_eq!(EpochSeconds::<SystemTime>::from_attr(value), Ok(time));
+    }
+
+    #[test]
+    fn epoch_millis_truncate_and_round_trip() {
+        let time =
+            EpochMillis::new(UNIX_EPOCH + Duration::new(10, 123_456_789)).expect("valid time");
+        assert_eq!(
+            time.into_inner(),
+            UNIX_EPOCH + Duration::from_millis(10_123)
+        );
+        let value = time.into_attr();
+        assert_eq!(value.n, Some("10123".into()));
+        assert_eq!(EpochMillis::<SystemTime>::from_attr(value), Ok(time));
+    }
+
+    #[test]
+    fn epoch_units_order_chronologically() {
+        let earlier = EpochMillis::new(UNIX_EPOCH + Duration::from_millis(999)).unwrap();
+        let later = EpochMillis::new(UNIX_EPOCH + Duration::from_millis(1_000)).unwrap();
+        assert!(earlier < later);
+    }
+
+    #[test]
+    fn epoch_units_reject_out_of_range_times() {
+        assert!(EpochSeconds::new(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+        assert!(EpochMillis::new(UNIX_EPOCH - Duration::from_millis(1)).is_err());
+        assert!(EpochSeconds::new(UNIX_EPOCH + Duration::from_secs(MAX_SECONDS + 1)).is_err());
+        for invalid in &["-1", "1.5", "soon", "18446744073709551615"] {
+            assert_eq!(
+                EpochSeconds::<SystemTime>::from_attr(n(invalid)),
+                Err(AttributeError::InvalidFormat)
+            );
+        }
+        assert_eq!(
+            EpochMillis::<SystemTime>::from_attr(AttributeValue::default()),
+            Err(AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn epoch_units_wrap_chrono() {
+        use chrono::offset::TimeZone;
+        let time = EpochMillis::new(Utc.ymd(2020, 9, 13).and_hms_milli(12, 26, 40, 5))
+            .expect("valid time");
+        let value = time.into_attr();
+        assert_eq!(value.n, Some("1600000000005".into()));
+        assert_eq!(EpochMillis::<DateTime<Utc>>::from_attr(value), Ok(time));
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn epoch_units_wrap_time() {
+        let stamp = EpochSeconds::new(
+            time::OffsetDateTime::from_unix_timestamp(1_600_000_000).expect("valid time"),
+        )
+        .expect("valid time");
+        let value = stamp.into_attr();
+        assert_eq!(value.n, Some("1600000000".into()));
+        assert_eq!(
+            EpochSeconds::<time::OffsetDateTime>::from_attr(value),
+            Ok(stamp)
+        );
+    }
+
+    #[test]
+    fn none_is_null() {
+        let value = into_attr(None::<SystemTime>);
+        assert_eq!(value.null, Some(true));
+        assert_eq!(from_attr::<Option<SystemTime>>(value), Ok(None));
+    }
+}
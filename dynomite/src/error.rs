@@ -7,6 +7,12 @@ pub enum AttributeError {
     /// Will be returned if an AttributeValue is present, and is of the expected
     /// type but its contents are not well-formatted
     InvalidFormat,
+    /// Will be returned if an AttributeValue is present, and is of the expected
+    /// type but its contents could not be parsed for a described reason
+    InvalidValue {
+        /// Description of why the value is invalid
+        message: String,
+    },
     /// Will be returned if provided AttributeValue is not of the expected type
     InvalidType,
     /// Will be returned if provided attributes does not included an
@@ -15,6 +21,12 @@ pub enum AttributeError {
         /// Name of the field that is missing
         name: String,
     },
+    /// Will be returned if provided attributes include a name
+    /// not declared by a type which denies unknown fields
+    UnknownField {
+        /// Name of the field that is unknown
+        name: String,
+    },
 }
 
 impl fmt::Display for AttributeError {
@@ -24,8 +36,10 @@ impl fmt::Display for AttributeError {
     ) -> fmt::Result {
         match self {
             AttributeError::InvalidFormat => write!(f, "Invalid format"),
+            AttributeError::InvalidValue { message } => write!(f, "Invalid value: {}", message),
             AttributeError::InvalidType => write!(f, "Invalid type"),
             AttributeError::MissingField { name } => write!(f, "Missing field {}", name),
+            AttributeError::UnknownField { name } => write!(f, "Unknown field {}", name),
         }
     }
 }
@@ -51,6 +65,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn invalid_value_displays() {
+        assert_eq!(
+            "Invalid value: foo",
+            format!(
+                "{}",
+                AttributeError::InvalidValue {
+                    message: "foo".into()
+                }
+            )
+        )
+    }
+
     #[test]
     fn invalid_type_displays() {
         assert_eq!("Invalid type", format!("{}", AttributeError::InvalidType))
@@ -63,4 +90,12 @@ mod tests {
             format!("{}", AttributeError::MissingField { name: "foo".into() })
         )
     }
+
+    #[test]
+    fn unknown_field_displays() {
+        assert_eq!(
+            "Unknown field foo",
+            format!("{}", AttributeError::UnknownField { name: "foo".into() })
+        )
+    }
 }
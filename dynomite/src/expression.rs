@@ -0,0 +1,107 @@
+//! Support for expression attribute names
+//!
+//! DynamoDB expressions may not refer to attributes whose names are reserved words or
+//! contain special characters directly. [Names](struct.Names.html) substitutes a
+//! `#segN` placeholder for each segment of a dotted document path, such as
+//! `profile.name`, collecting the substitutions as `ExpressionAttributeNames`.
+//! List indexes, such as `tags[0]`, are kept as is.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::expression::Names;
+//!
+//! let mut names = Names::new();
+//! assert_eq!(names.path("profile.name"), "#seg0.#seg1");
+//! assert_eq!(names.path("profile.tags[0]"), "#seg0.#seg2[0]");
+//! let names = names.into_inner();
+//! assert_eq!(names["#seg0"], "profile");
+//! assert_eq!(names["#seg2"], "tags");
+//! ```
+use std::collections::HashMap;
+
+/// Expression attribute names collected while building an expression
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Names {
+    placeholders: HashMap<String, String>,
+    names: HashMap<String, String>,
+}
+
+impl Names {
+    /// Returns an empty set of expression attribute names
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a document path with each segment of `path` replaced by its placeholder.
+    /// Segments already named by this set reuse their placeholder
+    pub fn path(
+        &mut self,
+        path: &str,
+    ) -> String {
+        path.split('.')
+            .map(|segment| {
+                let (name, indexes) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+                format!("{}{}", self.placeholder(name), indexes)
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn placeholder(
+        &mut self,
+        name: &str,
+    ) -> String {
+        if let Some(placeholder) = self.placeholders.get(name) {
+            return placeholder.clone();
+        }
+        let placeholder = format!("#seg{}", self.placeholders.len());
+        self.placeholders
+            .insert(name.to_string(), placeholder.clone());
+        self.names.insert(placeholder.clone(), name.to_string());
+        placeholder
+    }
+
+    /// Returns the collected names, keyed by placeholder, suitable for an
+    /// `expression_attribute_names` field
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_each_segment() {
+        let mut names = Names::new();
+        assert_eq!(names.path("name"), "#seg0");
+        assert_eq!(names.path("profile.name"), "#seg1.#seg0");
+        assert_eq!(
+            names.into_inner(),
+            vec![
+                ("#seg0".to_string(), "name".to_string()),
+                ("#seg1".to_string(), "profile".to_string())
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn escapes_reserved_words() {
+        let mut names = Names::new();
+        assert_eq!(names.path("size.comment"), "#seg0.#seg1");
+        let names = names.into_inner();
+        assert_eq!(names["#seg0"], "size");
+        assert_eq!(names["#seg1"], "comment");
+    }
+
+    #[test]
+    fn keeps_list_indexes() {
+        let mut names = Names::new();
+        assert_eq!(names.path("tags[1][2].name"), "#seg0[1][2].#seg1");
+        assert_eq!(names.into_inner()["#seg0"], "tags");
+    }
+}
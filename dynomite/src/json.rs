@@ -0,0 +1,57 @@
+//! Support for storing values as JSON strings
+//!
+//! Fields marked with `#[dynomite(json)]` are stored as JSON encoded `S`
+//! attribute values using [into_attr](fn.into_attr.html) and
+//! [from_attr](fn.from_attr.html) rather than their `Attribute` impl.
+//! This is useful for deeply nested data which is never filtered on.
+use crate::{AttributeError, AttributeValue};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Returns a JSON encoded `S` attribute value
+///
+/// # Panics
+///
+/// Panics if the value can not be represented as JSON, for instance maps
+/// with non-string keys
+pub fn into_attr<T: Serialize>(value: T) -> AttributeValue {
+    AttributeValue {
+        s: Some(serde_json::to_string(&value).expect("value is not representable as JSON")),
+        ..AttributeValue::default()
+    }
+}
+
+/// Parses a JSON encoded `S` attribute value
+pub fn from_attr<T: DeserializeOwned>(value: AttributeValue) -> Result<T, AttributeError> {
+    value.s.ok_or(AttributeError::InvalidType).and_then(|s| {
+        serde_json::from_str(&s).map_err(|err| AttributeError::InvalidValue {
+            message: err.to_string(),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn round_trips() {
+        let mut value = BTreeMap::new();
+        value.insert("foo".to_string(), vec![1, 2, 3]);
+        let attr = into_attr(&value);
+        assert_eq!(attr.s, Some(r#"{"foo":[1,2,3]}"#.into()));
+        assert_eq!(from_attr(attr), Ok(value));
+    }
+
+    #[test]
+    fn preserves_error_messages() {
+        let attr = AttributeValue {
+            s: Some("{".into()),
+            ..AttributeValue::default()
+        };
+        match from_attr::<BTreeMap<String, u32>>(attr) {
+            Err(AttributeError::InvalidValue { message }) => assert!(!message.is_empty()),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -35,6 +35,165 @@
 //! }
 //! ```
 //!
+//! ## Derived items in depth
+//!
+//! The attributes accepted by `#[derive(Item)]` and `#[derive(Attributes)]` are listed on the
+//! derives themselves. The more involved ones are described here.
+//!
+//! ### Keys
+//!
+//! Partition and sort key fields must be String, Number or Binary types, including enums deriving
+//! `Attribute`. Tuples, arrays other than `[u8; N]` and collections other than `Vec<u8>` are
+//! rejected at compile time. Custom types, such as scalar newtypes, are allowed as their
+//! representation can't be known at compile time. Key field types must implement `Clone`,
+//! `Debug` and `PartialEq`, which are derived for the generated `{Name}Key` struct. A
+//! `key_as` type must implement `From` the field's type. Key values are encoded by their
+//! type's `Attribute` impl alone, so key fields may not be marked `ttl`,
+//! `epoch`, `lenient_number`, `json`, `compress`, `map`, `encrypt_with` or `null_when_default`.
+//! `dynomite::epoch::EpochSeconds` and `EpochMillis` store numeric timestamp keys instead.
+//!
+//! Several `#[dynomite(sort_key, order = N)]` fields whose types implement `ToString` and
+//! `FromStr` are joined into one composite `S` sort key, such as `ORDER#123`. Orders must be
+//! unique and contiguous, starting from 0. The attribute is named `sk` and its components are
+//! separated by `#` unless `sort_key_name` or `sort_key_separator` say otherwise. Only the last
+//! component may contain the separator.
+//!
+//! `key_string` joins the `ToString` values of the partition key and each sort key field with
+//! the `key_delimiter`. `{Name}Key::parse_key_string` parses them back for key types
+//! implementing `FromStr`. Each `\` or first character of the delimiter in a value is escaped
+//! with a `\`, so that every key string parses back into the key it was made of.
+//!
+//! A `key_struct` must have fields matching the item's key fields by name and type. Its own
+//! attribute names must also match the item's, so a key field's `rename` must be repeated on
+//! it. It can't be combined with `skip_key_struct`, `key_hash` or a `flatten, key` field.
+//!
+//! `#[dynomite(flatten, key)]` may mark one unprefixed `flatten` field whose type derives
+//! `Item`. `Item::key` delegates to that field and `{Name}Key` is an alias of its key struct.
+//! Such items may not declare keys of their own and don't generate a `{Name}Query` builder.
+//!
+//! `schema()` infers key attribute types from the names of the key field types. Numbers,
+//! including `Decimal` and `BigDecimal`, and `EpochSeconds` or `EpochMillis` timestamps are `N`.
+//! `Vec<u8>`, `[u8; N]`, `Bytes`, `BytesMut` and `BinaryUuid` are `B`. All other types,
+//! including composite sort keys, are `S`.
+//!
+//! ### Queries and scans
+//!
+//! `{Name}Query::partition(value)` starts a query. It may be narrowed with `sort_eq(value)`,
+//! `sort_between(start, end)` and, for `String` and composite sort keys, `sort_begins_with(prefix)`.
+//! `limit(n)`, `index(name)` and `scan_forward(bool)` set the rest of the query, and
+//! `into_input(table)` turns it into a `QueryInput`. Generic items don't generate a builder.
+//!
+//! Scan filter functions return a `FilterExpression` fragment along with its
+//! `ExpressionAttributeNames` and `ExpressionAttributeValues`, filtering on a single attribute.
+//!
+//! ### Renaming
+//!
+//! `rename_all` follows one of the `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`,
+//! `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or `SCREAMING-KEBAB-CASE` conventions.
+//! `{Name}Key` structs resolve their field names the same way. `rename_all_serialize` and
+//! `rename_all_deserialize` override it for writing or reading. Fields are still read under
+//! their written names first, so that items written under an old convention remain readable
+//! while a schema migrates.
+//!
+//! Aliases are tried after the primary name, in declaration order, before falling back to
+//! `default` or failing. They are removed while reading, but values are always written under
+//! the primary name. Each attribute name, including aliases, may belong to only one field.
+//!
+//! ### Defaults and presence
+//!
+//! `default_value` string literals are converted with the field type's `From<&str>` impl.
+//!
+//! `null_when_default` applies to non-key, non-flatten fields implementing `Default` and
+//! `PartialEq`. Values equal to their `Default` are written as `NULL` attributes, which are read
+//! back as the `Default`. Absent attributes are still handled as they would be without it, for
+//! consumers which tell `NULL` apart from omission.
+//!
+//! `track_presence` applies to `default` or `Option` fields. The names of those whose attribute
+//! was present when read are collected into the item's `presence` field, which is never
+//! stored. This tells stored zero values apart from applied defaults.
+//!
+//! ### Reading and writing fields separately
+//!
+//! `read_only` fields are read but never written. A deprecated attribute may then be read
+//! during a migration while it disappears from items as they are written back. They resolve
+//! their `Default` when absent.
+//!
+//! `skip_serializing` fields, mirroring serde, are never written either but are read as any
+//! other field. They fail with `AttributeError::MissingField` when absent unless they are also
+//! marked `default`.
+//!
+//! `skip_deserializing` fields are written but never read, such as a derived attribute which
+//! only other consumers of the table query. They always resolve their `Default`, or their
+//! `default_value` literal, and may not be flattened.
+//!
+//! None of these may be keys, and fields which are never written are omitted from `{Name}Update`
+//! structs. A field which is neither read nor written is never stored.
+//!
+//! ### Flattening
+//!
+//! `flatten(prefix = "prefix_")` prefixes each hoisted attribute name, so that one type may be
+//! flattened more than once. A prefixed `HashMap<String, T>` or `BTreeMap<String, T>` whose
+//! values implement `Attribute` stores each of its entries as an attribute named by the prefix
+//! followed by the entry's key. Every attribute starting with the prefix is collected into it
+//! when read.
+//!
+//! `try_into_attributes` converts flattened fields with their own `try_into_attributes`
+//! method, which derived types provide.
+//!
+//! ### Encoding
+//!
+//! `encrypt_with` modules provide
+//! `fn encrypt(plain: AttributeValue) -> Result<AttributeValue, AttributeError>`, applied after a
+//! field is converted into an attribute value, and
+//! `fn decrypt(cipher: AttributeValue) -> Result<AttributeValue, AttributeError>`, applied before
+//! it is converted back. Decryption errors are returned from `from_attrs` and encryption errors
+//! from `try_into_attributes`. Infallible `Into<Attributes>` conversions panic on encryption
+//! errors. dynomite does not provide any encryption algorithms itself. Encrypted fields have no
+//! scan filter functions and are omitted from `{Name}Update` structs, as neither could report
+//! encryption errors.
+//!
+//! `ttl` fields implement `dynomite::ttl::Ttl` and `epoch` fields `dynomite::epoch::Epoch`, such
+//! as `SystemTime`, `DateTime<Utc>` or `Option`s of those. `ttl` stores whole epoch seconds in an
+//! `N` attribute, and `epoch` fractional seconds with nanosecond precision. Without either,
+//! these types are stored as rfc3339 formatted `S` attributes.
+//!
+//! `lenient_number` fields are always written as `N` attributes. Compressed fields still read
+//! uncompressed stored values as is.
+//!
+//! ### Generated methods
+//!
+//! `update_struct` generates an `update_expression(&self) -> (String, Attributes)` method. It
+//! `SET`s fields which are `Some` and `REMOVE`s `Option` fields which are `Some(None)`. Fields
+//! must implement `Clone`, `Debug` and `PartialEq`. It isn't supported for generic items or
+//! alongside `flatten` fields.
+//!
+//! `merge` overwrites each field with `other`'s value unless that is `None` or, for fields which
+//! aren't `Option`s, its type's `Default`. Those fields must implement `PartialEq` and `Default`.
+//! Partition and sort key fields are never overwritten. `changed_fields` requires every field to
+//! implement `PartialEq`, and `derive_default` every field to implement `Default`.
+//!
+//! `version` fields are non-key primitive integers. They generate
+//! `condition_expression_for_save(&self) -> (String, HashMap<String, String>, Attributes)` and
+//! `increment_version(&mut self)`, which wraps around after the integer type's maximum.
+//! `created_at` and `updated_at` fields implement `dynomite::Timestamp`.
+//!
+//! `crud` requires a `table` attribute. Its methods fail with a `dynomite::crud::CrudError`
+//! wrapping either the rusoto error of the request or the `AttributeError` of a response which
+//! could not be converted into the item.
+//!
+//! `deny_unknown_fields` fails with `AttributeError::UnknownField` and isn't supported
+//! alongside unprefixed `flatten` fields. `crate` also applies to `#[derive(Attributes)]` and
+//! `#[derive(Attribute)]`, for crates which only depend on dynomite through a re-export.
+//!
+//! ### Conditional and marker fields
+//!
+//! Fields may be conditionally compiled with `#[cfg(...)]` attributes. These are repeated on the
+//! code generated for them, including the fields of the `{Name}Key` struct.
+//!
+//! `PhantomData` fields are never stored and always resolve as `PhantomData`, permitting generic
+//! typed-marker items like `Document<Schema>`. Any bounds the generated impls require of generic
+//! parameters must be declared on the item itself.
+//!
 //! ## Rusoto extensions
 //!
 //! By importing the [dynomite::DynamoDbExt](trait.DynamoDbExt.html) trait, dynomite
@@ -60,19 +219,74 @@
 //! Enabled by default, the `uuid` feature adds support for implementing `Attribute` for
 //! the [uuid](https://crates.io/crates/uuid) crate's type `Uuid`, a useful
 //! type for producing and representing
-//! unique identifiers for items that satisfy [effective characteristics for partition keys](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/bp-partition-key-design.html)
+//! unique identifiers for items that satisfy [effective characteristics for partition keys](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/bp-partition-key-design.html).
+//! [`BinaryUuid`](struct.BinaryUuid.html) stores them in their compact 16 byte form instead.
 //!
 //! ## chrono
 //!
 //! Enabled by default, the `chrono` feature adds an implementation of `Attribute` for
-//! the std's [SystemTime](https://doc.rust-lang.org/std/time/struct.SystemTime.html) and chrono [`DateTime`](https://docs.rs/chrono/0.4.11/chrono/struct.DateTime.html) types which
+//! the std's [SystemTime](https://doc.rust-lang.org/std/time/struct.SystemTime.html) and chrono [`DateTime`](https://docs.rs/chrono/0.4.11/chrono/struct.DateTime.html),
+//! `NaiveDateTime` and `NaiveDate` types which
 //! internally use [rfc3339 timestamps](https://www.ietf.org/rfc/rfc3339.txt).
+//! Sub-second precision is retained and, for a given offset, the stored strings sort in
+//! chronological order so that they may serve as sort keys.
+//! `DateTime<Utc>` fields may instead be stored as fractional epoch seconds with the
+//! `#[dynomite(epoch)]` field attribute.
+//!
+//! ## time
+//!
+//! Disabled by default, the `time` feature adds an implementation of `Attribute` for
+//! the [time](https://crates.io/crates/time) crate's `OffsetDateTime`, `PrimitiveDateTime`
+//! and `Date` types which internally use [rfc3339 timestamps](https://www.ietf.org/rfc/rfc3339.txt),
+//! without an offset for the latter two. It may be enabled alongside the `chrono` feature.
+//!
+//! ## rust_decimal
+//!
+//! Disabled by default, the `rust_decimal` feature adds an implementation of `Attribute` for
+//! the [rust_decimal](https://crates.io/crates/rust_decimal) crate's `Decimal` type, which
+//! stores exact decimal numbers, such as monetary amounts, as `N` attributes.
+//!
+//! ## bigdecimal
+//!
+//! Disabled by default, the `bigdecimal` feature adds an implementation of `Attribute` for
+//! the [bigdecimal](https://crates.io/crates/bigdecimal) crate's arbitrary precision
+//! `BigDecimal` type, limited to the precision and magnitude DynamoDB numbers support.
 //!
 //! ## derive
 //!
 //! Enabled by default, the `derive` feature enables the use of the dynomite derive feature which
 //! allows you to simply add `#[derive(Item)]` to your structs.
 //!
+//! ## inputs
+//!
+//! Enabled by default, the `inputs` feature adds `put_input` and `get_input` methods to
+//! derived `Item`s, which return rusoto's `PutItemInput` and `GetItemInput` for a named table.
+//! It is implied by the `crud` feature.
+//!
+//! ## gzip
+//!
+//! Disabled by default, the `gzip` feature adds support for the `#[dynomite(compress = "gzip")]`
+//! field attribute, which stores large values as gzip compressed binary attributes using
+//! the [flate2](https://crates.io/crates/flate2) crate.
+//!
+//! ## serde_json
+//!
+//! Disabled by default, the `serde_json` feature adds support for the `#[dynomite(json)]`
+//! field attribute, which stores types implementing serde's `Serialize` and `Deserialize`
+//! traits as JSON encoded strings using the [serde_json](https://crates.io/crates/serde_json) crate.
+//!
+//! ## proptest
+//!
+//! Disabled by default, the `proptest` feature adds the [roundtrip](roundtrip/index.html)
+//! module, which tests that items convert back from their attributes unchanged, optionally
+//! against values generated by the [proptest](https://crates.io/crates/proptest) crate.
+//!
+//! ## crud
+//!
+//! Disabled by default, the `crud` feature adds support for the `#[dynomite(crud)]`
+//! container attribute, which generates async `get`, `save` and `delete` methods for items
+//! stored in the table named by `#[dynomite(table = "...")]`.
+//!
 //! ## rustls
 //!
 //! Disabled by default, the `rustls` feature overrides Rusoto's default tls
@@ -97,30 +311,65 @@ pub use rusoto_dynamodb_default as dynamodb;
 #[cfg(feature = "rustls")]
 pub use rusoto_dynamodb_rustls as dynamodb;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 #[cfg(feature = "chrono")]
 use chrono::{
     offset::{FixedOffset, Local},
-    DateTime, Utc,
+    DateTime, NaiveDate, NaiveDateTime, Utc,
 };
 
 // we re-export this because we
 // refer to it with in derive macros
+#[cfg(feature = "bigdecimal")]
+use bigdecimal::BigDecimal;
 #[doc(hidden)]
 pub use dynamodb::AttributeValue;
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use std::{
     borrow::Cow,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    convert::TryFrom,
+    ffi::OsString,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU8,
+    },
+    path::PathBuf,
+    rc::Rc,
+    sync::Arc,
     time::SystemTime,
 };
+#[cfg(feature = "time")]
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, OffsetDateTime, PrimitiveDateTime,
+};
 #[cfg(feature = "uuid")]
 use uuid::Uuid;
 
+pub mod composite;
+#[cfg(feature = "gzip")]
+pub mod compress;
+#[cfg(feature = "crud")]
+pub mod crud;
+pub mod epoch;
 pub mod error;
+pub mod expression;
 mod ext;
+#[cfg(feature = "serde_json")]
+pub mod json;
 pub mod retry;
+#[cfg(feature = "proptest")]
+pub mod roundtrip;
+pub mod schema;
+pub mod size;
+pub mod timestamp;
+pub mod ttl;
 
-pub use crate::{ext::DynamoDbExt, retry::Retries};
+pub use crate::{ext::DynamoDbExt, retry::Retries, schema::Schema, timestamp::Timestamp};
 
 pub use crate::error::AttributeError;
 /// Type alias for map of named attribute values
@@ -221,10 +470,53 @@ pub type Attributes = HashMap<String, AttributeValue>;
 ///     #[dynomite(partition_key)]
 ///     id: String,
 ///     #[dynomite(default)]
+///     pages: u32,
+/// }
+/// ```
+///
+/// `Option` fields don't need this attribute. Both absent and `NULL` attribute
+/// values are resolved as `None`.
+///
+/// ```
+/// use dynomite::Item;
+///
+/// #[derive(Item)]
+/// struct Book {
+///     #[dynomite(partition_key)]
+///     id: String,
 ///     summary: Option<String>,
 /// }
 /// ```
 ///
+/// ## Flattening nested attributes
+///
+/// Fields whose types derive `Attributes` are stored as nested `M` attribute values by default.
+/// You can use the `flatten` field attribute to instead hoist their attributes into the
+/// parent item. When flattening the same type more than once, use `flatten(prefix = "...")`
+/// to avoid attribute name collisions.
+///
+/// ```
+/// use dynomite::{Attributes, Item};
+///
+/// #[derive(Attributes)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(Item)]
+/// struct Customer {
+///     #[dynomite(partition_key)]
+///     id: String,
+///     // stored as `billing_street` and `billing_city`
+///     #[dynomite(flatten(prefix = "billing_"))]
+///     billing: Address,
+///     // stored as `shipping_street` and `shipping_city`
+///     #[dynomite(flatten(prefix = "shipping_"))]
+///     shipping: Address,
+/// }
+/// ```
+///
 /// ## Item attribute projections
 ///
 /// DynamoDB `Item`s are a set of attributes with a uniquely identifying
@@ -245,7 +537,29 @@ pub trait Item: Into<Attributes> + FromAttributes {
     /// Returns the set of attributes which make up this item's primary key
     ///
     /// This is often used in item look ups
+    #[must_use]
     fn key(&self) -> Attributes;
+
+    /// Consumes this item, returning the set of attributes which make up its primary key
+    ///
+    /// Unlike `key`, derived implementations move key fields rather than cloning them,
+    /// which avoids copying large key values when the item is not needed afterwards
+    #[must_use]
+    fn into_key(self) -> Attributes {
+        self.key()
+    }
+
+    /// Returns the approximate size in bytes of this item as stored in DynamoDB
+    ///
+    /// This is useful for guarding against DynamoDB's
+    /// [size::MAX_ITEM_SIZE](size/constant.MAX_ITEM_SIZE.html) limit before writing items.
+    /// The item is borrowed through its `From<&Self>` conversion, which derived items provide
+    fn approximate_size(&self) -> usize
+    where
+        for<'a> Attributes: From<&'a Self>,
+    {
+        size::item(&Attributes::from(self))
+    }
 }
 
 /// A type capable of being converted into an or from and AWS `AttributeValue`
@@ -272,6 +586,12 @@ pub trait Attribute: Sized {
     fn into_attr(self: Self) -> AttributeValue;
     /// Returns a fallible conversion from an `AttributeValue`
     fn from_attr(value: AttributeValue) -> Result<Self, AttributeError>;
+    /// Returns a fallible conversion into an `AttributeValue`, failing for values which
+    /// DynamoDB would reject, such as non-finite floats. The default implementation
+    /// delegates to `into_attr`
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        Ok(self.into_attr())
+    }
 }
 
 /// A type capable of being produced from
@@ -281,6 +601,17 @@ pub trait FromAttributes: Sized {
     /// of a `String` keys and `AttributeValues`. If
     /// a instance can not be resolved and `AttributeError` will be returned.
     fn from_attrs(attrs: Attributes) -> Result<Self, AttributeError>;
+
+    /// Returns an instance resolved from a borrowed collection of attributes, removing
+    /// only those it consumes and leaving the rest for other types to resolve. Attributes
+    /// may have been removed even if an `AttributeError` is returned.
+    ///
+    /// The default implementation consumes every attribute. Types deriving `Item` or
+    /// `Attributes` remove only the attributes of their fields, without enforcing
+    /// `#[dynomite(deny_unknown_fields)]`
+    fn from_attrs_mut(attrs: &mut Attributes) -> Result<Self, AttributeError> {
+        Self::from_attrs(std::mem::take(attrs))
+    }
 }
 
 /// Coerces a homogenious HashMap of attribute values into a homogeneous Map of types
@@ -310,22 +641,79 @@ impl<A: Attribute> FromAttributes for BTreeMap<String, A> {
     }
 }
 
-/// A Map type for Items, represented as the `M` AttributeValue type
-impl<T: Item> Attribute for T {
+/// A boxed value, represented by the boxed type's `AttributeValue` type
+///
+/// This permits recursive types such as `parent: Option<Box<Category>>`
+impl<T: Attribute> Attribute for Box<T> {
     fn into_attr(self: Self) -> AttributeValue {
-        AttributeValue {
-            m: Some(self.into()),
-            ..AttributeValue::default()
-        }
+        (*self).into_attr()
     }
     fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
-        value
-            .m
-            .ok_or(AttributeError::InvalidType)
-            .and_then(T::from_attrs)
+        T::from_attr(value).map(Box::new)
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        (*self).try_into_attr()
     }
 }
 
+// shared values are unwrapped when uniquely owned, and cloned otherwise.
+// unsized strings and byte slices are stored as their owned counterparts
+macro_rules! shared_attr {
+    ($pointer:ident) => {
+        /// A shared value, represented by the shared type's `AttributeValue` type
+        impl<T: Attribute + Clone> Attribute for $pointer<T> {
+            fn into_attr(self: Self) -> AttributeValue {
+                $pointer::try_unwrap(self)
+                    .unwrap_or_else(|shared| (*shared).clone())
+                    .into_attr()
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                T::from_attr(value).map($pointer::new)
+            }
+            fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+                $pointer::try_unwrap(self)
+                    .unwrap_or_else(|shared| (*shared).clone())
+                    .try_into_attr()
+            }
+        }
+
+        /// A shared string, represented by the S AttributeValue type
+        impl Attribute for $pointer<str> {
+            fn into_attr(self: Self) -> AttributeValue {
+                AttributeValue {
+                    s: Some(self.to_string()),
+                    ..AttributeValue::default()
+                }
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                value
+                    .s
+                    .ok_or(AttributeError::InvalidType)
+                    .map($pointer::from)
+            }
+        }
+
+        /// Shared bytes, represented by the B AttributeValue type
+        impl Attribute for $pointer<[u8]> {
+            fn into_attr(self: Self) -> AttributeValue {
+                AttributeValue {
+                    b: Some(self.to_vec().into()),
+                    ..AttributeValue::default()
+                }
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                value
+                    .b
+                    .ok_or(AttributeError::InvalidType)
+                    .map(|bytes| $pointer::from(bytes.as_ref()))
+            }
+        }
+    };
+}
+
+shared_attr!(Rc);
+shared_attr!(Arc);
+
 /// A Map type for Items for HashMaps, represented as the `M` AttributeValue type
 #[allow(clippy::implicit_hasher)]
 impl<A: Attribute> Attribute for HashMap<String, A> {
@@ -341,6 +729,16 @@ impl<A: Attribute> Attribute for HashMap<String, A> {
             .ok_or(AttributeError::InvalidType)
             .and_then(Self::from_attrs) // because FromAttributes is impl by all HashMap<String, A>
     }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        Ok(AttributeValue {
+            m: Some(
+                self.into_iter()
+                    .map(|(k, v)| v.try_into_attr().map(|v| (k, v)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ..AttributeValue::default()
+        })
+    }
 }
 
 /// A Map type for `Items` for `BTreeMaps`, represented as the `M` AttributeValue type
@@ -357,6 +755,99 @@ impl<A: Attribute> Attribute for BTreeMap<String, A> {
             .ok_or(AttributeError::InvalidType)
             .and_then(Self::from_attrs) // because FromAttributes is impl by all BTreeMap<String, A>
     }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        Ok(AttributeValue {
+            m: Some(
+                self.into_iter()
+                    .map(|(k, v)| v.try_into_attr().map(|v| (k, v)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+/// An `OsString`, represented by the `S` AttributeValue type when valid UTF-8 and otherwise by
+/// the `B` AttributeValue type holding its platform's encoding: raw bytes on unix platforms and
+/// little endian UTF-16 code units on windows. Binary values are thus only readable on the
+/// platform which wrote them. Other platforms store invalid UTF-8 lossily
+impl Attribute for OsString {
+    fn into_attr(self: Self) -> AttributeValue {
+        match self.into_string() {
+            Ok(s) => AttributeValue {
+                s: Some(s),
+                ..AttributeValue::default()
+            },
+            Err(value) => AttributeValue {
+                b: Some(os_string_into_bytes(value).into()),
+                ..AttributeValue::default()
+            },
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        match (value.s, value.b) {
+            (Some(s), _) => Ok(s.into()),
+            (_, Some(b)) => os_string_from_bytes(b.to_vec()),
+            _ => Err(AttributeError::InvalidType),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn os_string_into_bytes(value: OsString) -> Vec<u8> {
+    use std::os::unix::ffi::OsStringExt;
+    value.into_vec()
+}
+
+#[cfg(unix)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> Result<OsString, AttributeError> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(OsString::from_vec(bytes))
+}
+
+#[cfg(windows)]
+fn os_string_into_bytes(value: OsString) -> Vec<u8> {
+    use std::os::windows::ffi::OsStrExt;
+    let mut bytes = Vec::new();
+    for unit in value.encode_wide() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(windows)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> Result<OsString, AttributeError> {
+    use std::os::windows::ffi::OsStringExt;
+    if bytes.len() % 2 != 0 {
+        return Err(AttributeError::InvalidFormat);
+    }
+    let units = bytes
+        .chunks(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect::<Vec<_>>();
+    Ok(OsString::from_wide(&units))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_string_into_bytes(value: OsString) -> Vec<u8> {
+    value.to_string_lossy().into_owned().into_bytes()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_string_from_bytes(bytes: Vec<u8>) -> Result<OsString, AttributeError> {
+    String::from_utf8(bytes)
+        .map(OsString::from)
+        .map_err(|_| AttributeError::InvalidFormat)
+}
+
+/// A `PathBuf`, represented the same way as an `OsString`
+impl Attribute for PathBuf {
+    fn into_attr(self: Self) -> AttributeValue {
+        self.into_os_string().into_attr()
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        OsString::from_attr(value).map(PathBuf::from)
+    }
 }
 
 /// A `String` type for `Uuids`, represented by the `S` AttributeValue type
@@ -376,6 +867,69 @@ impl Attribute for Uuid {
     }
 }
 
+/// A `Uuid` stored in its compact 16 byte form, represented by the `B` AttributeValue type
+///
+/// Hyphenated `S` values, as written by `Uuid`'s own `Attribute` impl, are also accepted
+/// when reading to ease migrating attributes from the string representation
+///
+/// ```
+/// use dynomite::{Attribute, BinaryUuid};
+/// use uuid::Uuid;
+///
+/// let id = BinaryUuid::from(Uuid::new_v4());
+/// assert_eq!(id.into_attr().b.map(|b| b.len()), Some(16));
+/// assert_eq!(BinaryUuid::from_attr(id.0.into_attr()), Ok(id));
+/// ```
+#[cfg(feature = "uuid")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BinaryUuid(pub Uuid);
+
+#[cfg(feature = "uuid")]
+impl From<Uuid> for BinaryUuid {
+    fn from(uuid: Uuid) -> Self {
+        BinaryUuid(uuid)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<BinaryUuid> for Uuid {
+    fn from(uuid: BinaryUuid) -> Self {
+        uuid.0
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl std::ops::Deref for BinaryUuid {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Attribute for BinaryUuid {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            b: Some(Bytes::copy_from_slice(self.0.as_bytes())),
+            ..AttributeValue::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        match value {
+            AttributeValue { b: Some(b), .. } => Uuid::from_slice(&b)
+                .map(BinaryUuid)
+                .map_err(|_| AttributeError::InvalidFormat),
+            AttributeValue { s: Some(s), .. } => Uuid::from_attr(AttributeValue {
+                s: Some(s),
+                ..AttributeValue::default()
+            })
+            .map(BinaryUuid),
+            _ => Err(AttributeError::InvalidType),
+        }
+    }
+}
+
 /// An `rfc3339` formatted version of `DateTime<Utc>`, represented by the `S` AttributeValue type
 #[cfg(feature = "chrono")]
 impl Attribute for DateTime<Utc> {
@@ -440,6 +994,236 @@ impl Attribute for DateTime<FixedOffset> {
     }
 }
 
+// rfc3339 without an offset whose fractional seconds, if any, have 3, 6 or 9 digits
+#[cfg(feature = "chrono")]
+const NAIVE_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// An `rfc3339` formatted version of `NaiveDateTime` without an offset, represented by the `S` AttributeValue type
+#[cfg(feature = "chrono")]
+impl Attribute for NaiveDateTime {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            s: Some(self.format(NAIVE_DATE_TIME_FORMAT).to_string()),
+            ..Default::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value.s.ok_or(AttributeError::InvalidType).and_then(|s| {
+            NaiveDateTime::parse_from_str(&s, NAIVE_DATE_TIME_FORMAT)
+                .map_err(|_| AttributeError::InvalidFormat)
+        })
+    }
+}
+
+/// An `rfc3339` formatted full date version of `NaiveDate`, represented by the `S` AttributeValue type
+#[cfg(feature = "chrono")]
+impl Attribute for NaiveDate {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            s: Some(self.format("%Y-%m-%d").to_string()),
+            ..Default::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value.s.ok_or(AttributeError::InvalidType).and_then(|s| {
+            NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| AttributeError::InvalidFormat)
+        })
+    }
+}
+
+// rfc3339 without an offset, the fractional seconds of which omit trailing zeros
+#[cfg(feature = "time")]
+const PRIMITIVE_DATE_TIME_FORMAT: &[FormatItem<'_>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]");
+
+#[cfg(feature = "time")]
+const DATE_FORMAT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+
+// rfc3339 timestamps have four digit years
+#[cfg(feature = "time")]
+fn check_year(
+    year: i32,
+    value: impl std::fmt::Display,
+) -> Result<(), AttributeError> {
+    if (0..=9999).contains(&year) {
+        Ok(())
+    } else {
+        Err(AttributeError::InvalidValue {
+            message: format!(
+                "`{}` has a year outside of 0 to 9999, which rfc3339 can't represent",
+                value
+            ),
+        })
+    }
+}
+
+#[cfg(feature = "time")]
+fn invalid_time(
+    value: &str,
+    err: time::error::Parse,
+) -> AttributeError {
+    AttributeError::InvalidValue {
+        message: format!("`{}` is not a valid timestamp: {}", value, err),
+    }
+}
+
+/// An `rfc3339` formatted version of `OffsetDateTime`, represented by the `S` AttributeValue type.
+/// Both `Z` and numeric offsets are accepted when parsing. `try_into_attr` rejects years
+/// outside of 0 to 9999, which `into_attr` writes using their `Display` impl instead
+#[cfg(feature = "time")]
+impl Attribute for OffsetDateTime {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            s: Some(self.format(&Rfc3339).unwrap_or_else(|_| self.to_string())),
+            ..Default::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value
+            .s
+            .ok_or(AttributeError::InvalidType)
+            .and_then(|s| OffsetDateTime::parse(&s, &Rfc3339).map_err(|err| invalid_time(&s, err)))
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        check_year(self.year(), self)?;
+        Ok(self.into_attr())
+    }
+}
+
+/// An `rfc3339` formatted version of `PrimitiveDateTime` without an offset, represented by the `S` AttributeValue type.
+/// `try_into_attr` rejects years outside of 0 to 9999
+#[cfg(feature = "time")]
+impl Attribute for PrimitiveDateTime {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            s: Some(
+                self.format(PRIMITIVE_DATE_TIME_FORMAT)
+                    .unwrap_or_else(|_| self.to_string()),
+            ),
+            ..Default::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value.s.ok_or(AttributeError::InvalidType).and_then(|s| {
+            PrimitiveDateTime::parse(&s, PRIMITIVE_DATE_TIME_FORMAT)
+                .map_err(|err| invalid_time(&s, err))
+        })
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        check_year(self.year(), self)?;
+        Ok(self.into_attr())
+    }
+}
+
+/// An `rfc3339` formatted full date version of `Date`, represented by the `S` AttributeValue type.
+/// `try_into_attr` rejects years outside of 0 to 9999
+#[cfg(feature = "time")]
+impl Attribute for Date {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            s: Some(
+                self.format(DATE_FORMAT)
+                    .unwrap_or_else(|_| self.to_string()),
+            ),
+            ..Default::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value
+            .s
+            .ok_or(AttributeError::InvalidType)
+            .and_then(|s| Date::parse(&s, DATE_FORMAT).map_err(|err| invalid_time(&s, err)))
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        check_year(self.year(), self)?;
+        Ok(self.into_attr())
+    }
+}
+
+/// An exact decimal number, represented by the `N` AttributeValue type.
+/// Numbers which can't be represented without losing precision, such as those with more
+/// than 28 significant digits, fail to parse with an `AttributeError::InvalidValue`
+/// including the stored number. Note that DynamoDB may not preserve trailing zeros
+#[cfg(feature = "rust_decimal")]
+impl Attribute for Decimal {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            n: Some(self.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value.n.ok_or(AttributeError::InvalidType).and_then(|n| {
+            Decimal::from_str_exact(&n).map_err(|err| AttributeError::InvalidValue {
+                message: format!("`{}` is not a valid decimal: {}", n, err),
+            })
+        })
+    }
+}
+
+// https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/HowItWorks.NamingRulesDataTypes.html#HowItWorks.DataTypes.Number
+#[cfg(feature = "bigdecimal")]
+const MAX_NUMBER_DIGITS: usize = 38;
+
+#[cfg(feature = "bigdecimal")]
+const NUMBER_EXPONENTS: std::ops::RangeInclusive<i64> = -130..=125;
+
+/// Returns why a number can't be stored by DynamoDB, if it can't
+#[cfg(feature = "bigdecimal")]
+fn unrepresentable(value: &BigDecimal) -> Option<String> {
+    let (digits, scale) = value.normalized().as_bigint_and_exponent();
+    let digits = digits.to_string().trim_start_matches('-').len();
+    // the power of ten of the most significant digit
+    let exponent = digits as i64 - 1 - scale;
+    if value == &BigDecimal::default() {
+        None
+    } else if digits > MAX_NUMBER_DIGITS {
+        Some(format!(
+            "`{}` has {} significant digits, more than the {} supported",
+            value, digits, MAX_NUMBER_DIGITS
+        ))
+    } else if !NUMBER_EXPONENTS.contains(&exponent) {
+        Some(format!(
+            "`{}` has a magnitude of 1E{}, outside of the supported 1E-130 to 1E+125",
+            value, exponent
+        ))
+    } else {
+        None
+    }
+}
+
+/// An arbitrary precision decimal number, represented by the `N` AttributeValue type.
+/// Numbers with more than 38 significant digits or a magnitude outside of 1E-130 to 1E+125
+/// fail to parse, and `try_into_attr` rejects them, with an `AttributeError::InvalidValue`
+/// describing why rather than leaving DynamoDB to reject them later
+#[cfg(feature = "bigdecimal")]
+impl Attribute for BigDecimal {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            n: Some(self.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        let n = value.n.ok_or(AttributeError::InvalidType)?;
+        let value = n
+            .parse::<BigDecimal>()
+            .map_err(|err| AttributeError::InvalidValue {
+                message: format!("`{}` is not a valid decimal: {}", n, err),
+            })?;
+        match unrepresentable(&value) {
+            Some(message) => Err(AttributeError::InvalidValue { message }),
+            None => Ok(value),
+        }
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        match unrepresentable(&self) {
+            Some(message) => Err(AttributeError::InvalidValue { message }),
+            None => Ok(self.into_attr()),
+        }
+    }
+}
+
 /// An `rfc3339` formatted version of `SystemTime`, represented by the `S` AttributeValue type
 #[cfg(feature = "chrono")]
 impl Attribute for SystemTime {
@@ -561,6 +1345,24 @@ impl Attribute for bytes::Bytes {
     }
 }
 
+// a Binary type, represented by the B AttributeValue type.
+// buffers are frozen in place when written but copied when read
+// as shared `Bytes` can not be reclaimed as mutable buffers
+impl Attribute for BytesMut {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            b: Some(self.freeze()),
+            ..AttributeValue::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        value
+            .b
+            .ok_or(AttributeError::InvalidType)
+            .map(|bytes| BytesMut::from(bytes.as_ref()))
+    }
+}
+
 // a Binary type, represented by the B AttributeValue type
 impl Attribute for Vec<u8> {
     fn into_attr(self: Self) -> AttributeValue {
@@ -600,6 +1402,46 @@ impl<A: Attribute> Attribute for Vec<A> {
             .map(Attribute::from_attr)
             .collect()
     }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        Ok(AttributeValue {
+            l: Some(
+                self.into_iter()
+                    .map(Attribute::try_into_attr)
+                    .collect::<Result<_, _>>()?,
+            ),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+/// A fixed-length byte array, such as a hash, represented by the B AttributeValue type
+impl<const N: usize> Attribute for [u8; N] {
+    fn into_attr(self: Self) -> AttributeValue {
+        AttributeValue {
+            b: Some(self.to_vec().into()),
+            ..AttributeValue::default()
+        }
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        let bytes = value.b.ok_or(AttributeError::InvalidType)?;
+        <[u8; N]>::try_from(bytes.as_ref()).map_err(|_| AttributeError::InvalidFormat)
+    }
+}
+
+/// A fixed-length list, represented by the L AttributeValue type
+///
+/// Stored lists of any other length are rejected as `InvalidFormat` when read
+impl<T: Attribute, const N: usize> Attribute for [T; N] {
+    fn into_attr(self: Self) -> AttributeValue {
+        Vec::from(self).into_attr()
+    }
+    fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+        let values = Vec::<T>::from_attr(value)?;
+        <[T; N]>::try_from(values).map_err(|_| AttributeError::InvalidFormat)
+    }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        Vec::from(self).try_into_attr()
+    }
 }
 
 impl<T: Attribute> Attribute for Option<T> {
@@ -618,6 +1460,12 @@ impl<T: Attribute> Attribute for Option<T> {
             _ => Ok(Some(Attribute::from_attr(value)?)),
         }
     }
+    fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+        match self {
+            Some(value) => value.try_into_attr(),
+            None => Ok(None::<T>.into_attr()),
+        }
+    }
 }
 
 macro_rules! numeric_attr {
@@ -639,6 +1487,36 @@ macro_rules! numeric_attr {
     };
 }
 
+// floats are only stored when finite, `NaN` and infinities are represented
+// by `into_attr` as strings DynamoDB rejects
+macro_rules! float_attr {
+    ($type:ty) => {
+        impl Attribute for $type {
+            fn into_attr(self) -> AttributeValue {
+                AttributeValue {
+                    n: Some(self.to_string()),
+                    ..AttributeValue::default()
+                }
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                value
+                    .n
+                    .ok_or(AttributeError::InvalidType)
+                    .and_then(|num| num.parse().map_err(|_| AttributeError::InvalidFormat))
+            }
+            fn try_into_attr(self) -> Result<AttributeValue, AttributeError> {
+                if self.is_finite() {
+                    Ok(self.into_attr())
+                } else {
+                    Err(AttributeError::InvalidValue {
+                        message: format!("{} is not a finite number", self),
+                    })
+                }
+            }
+        }
+    };
+}
+
 macro_rules! numeric_set_attr {
     ($type:ty => $collection:ty) => {
         /// A Number set type, represented by the NS AttributeValue type
@@ -661,15 +1539,109 @@ macro_rules! numeric_set_attr {
     };
 }
 
-// implement Attribute for numeric types
-numeric_attr!(u16);
-numeric_attr!(i16);
-numeric_attr!(u32);
-numeric_attr!(i32);
-numeric_attr!(u64);
-numeric_attr!(i64);
-numeric_attr!(f32);
-numeric_attr!(f64);
+// implement Attribute for numeric types
+numeric_attr!(u16);
+numeric_attr!(i16);
+numeric_attr!(u32);
+numeric_attr!(i32);
+numeric_attr!(u64);
+numeric_attr!(i64);
+// note DynamoDB numbers hold at most 38 significant digits,
+// fewer than the 39 of the largest 128 bit integers
+numeric_attr!(u128);
+numeric_attr!(i128);
+float_attr!(f32);
+float_attr!(f64);
+
+// stored zeros fail to parse as non-zero integers
+numeric_attr!(NonZeroU8);
+numeric_attr!(NonZeroI8);
+numeric_attr!(NonZeroU16);
+numeric_attr!(NonZeroI16);
+numeric_attr!(NonZeroU32);
+numeric_attr!(NonZeroI32);
+numeric_attr!(NonZeroU64);
+numeric_attr!(NonZeroI64);
+numeric_attr!(NonZeroU128);
+numeric_attr!(NonZeroI128);
+
+// network addresses are stored as written by their `Display` impls, including
+// ipv6 scope ids and ipv4 mapped addresses. Note ipv6 socket address flow info
+// isn't displayed and thus isn't stored
+macro_rules! address_attr {
+    ($type:ty, $description:expr) => {
+        /// A network address, represented by the `S` AttributeValue type
+        impl Attribute for $type {
+            fn into_attr(self: Self) -> AttributeValue {
+                AttributeValue {
+                    s: Some(self.to_string()),
+                    ..AttributeValue::default()
+                }
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                value.s.ok_or(AttributeError::InvalidType).and_then(|s| {
+                    s.parse().map_err(|_| AttributeError::InvalidValue {
+                        message: format!("`{}` is not a valid {}", s, $description),
+                    })
+                })
+            }
+        }
+    };
+}
+
+address_attr!(IpAddr, "ip address");
+address_attr!(Ipv4Addr, "ipv4 address");
+address_attr!(Ipv6Addr, "ipv6 address");
+address_attr!(SocketAddr, "socket address");
+address_attr!(SocketAddrV4, "ipv4 socket address");
+address_attr!(SocketAddrV6, "ipv6 socket address");
+
+// tuples are stored positionally, lists of any other length than
+// the tuple's arity are rejected as `InvalidFormat`
+macro_rules! tuple_attr {
+    ($len:expr => $($type:ident $value:ident),+) => {
+        /// A fixed-length list, represented by the `L` AttributeValue type
+        impl<$($type: Attribute),+> Attribute for ($($type,)+) {
+            fn into_attr(self: Self) -> AttributeValue {
+                let ($($value,)+) = self;
+                AttributeValue {
+                    l: Some(vec![$($value.into_attr()),+]),
+                    ..AttributeValue::default()
+                }
+            }
+            fn from_attr(value: AttributeValue) -> Result<Self, AttributeError> {
+                let values = value.l.ok_or(AttributeError::InvalidType)?;
+                if values.len() != $len {
+                    return Err(AttributeError::InvalidFormat);
+                }
+                let mut values = values.into_iter();
+                Ok(($($type::from_attr(
+                    values.next().ok_or(AttributeError::InvalidFormat)?,
+                )?,)+))
+            }
+            fn try_into_attr(self: Self) -> Result<AttributeValue, AttributeError> {
+                let ($($value,)+) = self;
+                Ok(AttributeValue {
+                    l: Some(vec![$($value.try_into_attr()?),+]),
+                    ..AttributeValue::default()
+                })
+            }
+        }
+    };
+}
+
+tuple_attr!(1 => A a);
+tuple_attr!(2 => A a, B b);
+tuple_attr!(3 => A a, B b, C c);
+tuple_attr!(4 => A a, B b, C c, D d);
+tuple_attr!(5 => A a, B b, C c, D d, E e);
+tuple_attr!(6 => A a, B b, C c, D d, E e, F f);
+tuple_attr!(7 => A a, B b, C c, D d, E e, F f, G g);
+tuple_attr!(8 => A a, B b, C c, D d, E e, F f, G g, H h);
+tuple_attr!(9 => A a, B b, C c, D d, E e, F f, G g, H h, I i);
+tuple_attr!(10 => A a, B b, C c, D d, E e, F f, G g, H h, I i, J j);
+tuple_attr!(11 => A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k);
+tuple_attr!(12 => A a, B b, C c, D d, E e, F f, G g, H h, I i, J j, K k, L l);
 
 // implement Attribute for numeric collections
 numeric_set_attr!(u16 => HashSet<u16>);
@@ -687,6 +1659,11 @@ numeric_set_attr!(i64 => BTreeSet<i64>);
 numeric_set_attr!(u64 => HashSet<u64>);
 numeric_set_attr!(u64 => BTreeSet<u64>);
 
+numeric_set_attr!(u128 => HashSet<u128>);
+numeric_set_attr!(u128 => BTreeSet<u128>);
+numeric_set_attr!(i128 => HashSet<i128>);
+numeric_set_attr!(i128 => BTreeSet<i128>);
+
 // note floats don't implement `Ord` and thus can't
 // be used in various XXXSet types
 //numeric_set_attr!(f32 => HashSet<f32>);
@@ -767,6 +1744,35 @@ mod test {
         assert_eq!(Ok(value), Uuid::from_attr(value.into_attr()));
     }
 
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn binary_uuid_attr() {
+        let value = BinaryUuid(Uuid::new_v4());
+        let attr = value.into_attr();
+        assert_eq!(attr.b.as_ref().map(|b| &b[..]), Some(&value.as_bytes()[..]));
+        assert_eq!(Ok(value), BinaryUuid::from_attr(attr));
+        assert_eq!(Ok(value), BinaryUuid::from_attr(value.0.into_attr()));
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn binary_uuid_invalid_attr() {
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            BinaryUuid::from_attr(AttributeValue {
+                b: Some(Bytes::from_static(&[1, 2, 3])),
+                ..AttributeValue::default()
+            })
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            BinaryUuid::from_attr(AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
     #[test]
     fn uuid_invalid_attr() {
         assert_eq!(
@@ -841,6 +1847,77 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_datetime_utc_subsec_attr() {
+        use chrono::offset::TimeZone;
+        let value = Utc.ymd(2020, 1, 2).and_hms_nano(3, 4, 5, 123_456_789);
+        assert_eq!(Ok(value), DateTime::<Utc>::from_attr(value.into_attr()));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_datetime_utc_attr_ordering() {
+        use chrono::offset::TimeZone;
+        let base = Utc.ymd(2020, 1, 2).and_hms(3, 4, 5);
+        let values = vec![
+            base,
+            base + chrono::Duration::nanoseconds(1),
+            base + chrono::Duration::milliseconds(1),
+            base + chrono::Duration::milliseconds(100),
+            base + chrono::Duration::seconds(1),
+            base + chrono::Duration::days(400),
+        ];
+        let strings = values
+            .into_iter()
+            .map(|value| value.into_attr().s.unwrap())
+            .collect::<Vec<_>>();
+        let mut sorted = strings.clone();
+        sorted.sort();
+        assert_eq!(strings, sorted);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_naive_datetime_attr() {
+        let value = NaiveDate::from_ymd(2015, 2, 18).and_hms_micro(23, 16, 9, 12);
+        assert_eq!(
+            value.into_attr().s,
+            Some("2015-02-18T23:16:09.000012".to_string())
+        );
+        assert_eq!(Ok(value), NaiveDateTime::from_attr(value.into_attr()));
+        let value = NaiveDate::from_ymd(2015, 2, 18).and_hms(23, 16, 9);
+        assert_eq!(Ok(value), NaiveDateTime::from_attr(value.into_attr()));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_naive_datetime_invalid_attr() {
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            NaiveDateTime::from_attr("2015-02-18".to_string().into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            NaiveDateTime::from_attr(AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn chrono_naive_date_attr() {
+        let value = NaiveDate::from_ymd(2015, 2, 18);
+        assert_eq!(value.into_attr().s, Some("2015-02-18".to_string()));
+        assert_eq!(Ok(value), NaiveDate::from_attr(value.into_attr()));
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            NaiveDate::from_attr("18/02/2015".to_string().into_attr())
+        );
+    }
+
     #[test]
     #[cfg(feature = "chrono")]
     fn system_time_attr() {
@@ -862,6 +1939,191 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_offset_date_time_attr() {
+        let value = OffsetDateTime::from_unix_timestamp_nanos(1_600_000_000_123_456_789)
+            .expect("valid timestamp");
+        assert_eq!(
+            value.into_attr().s,
+            Some("2020-09-13T12:26:40.123456789Z".to_string())
+        );
+        assert_eq!(Ok(value), OffsetDateTime::from_attr(value.into_attr()));
+        assert_eq!(
+            OffsetDateTime::from_attr(
+                "2020-09-13T14:26:40.123456789+02:00"
+                    .to_string()
+                    .into_attr()
+            ),
+            Ok(value)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_offset_date_time_invalid_attr() {
+        match OffsetDateTime::from_attr("2020-09-13".to_string().into_attr()) {
+            Err(AttributeError::InvalidValue { message }) => {
+                assert!(message.contains("`2020-09-13`"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            OffsetDateTime::from_attr(AttributeValue {
+                bool: Some(true),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_primitive_date_time_attr() {
+        let value = Date::from_calendar_date(2015, time::Month::February, 18)
+            .and_then(|date| date.with_hms_micro(23, 16, 9, 12))
+            .expect("valid date time");
+        assert_eq!(
+            value.into_attr().s,
+            Some("2015-02-18T23:16:09.000012".to_string())
+        );
+        assert_eq!(Ok(value), PrimitiveDateTime::from_attr(value.into_attr()));
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_date_attr() {
+        let value = Date::from_calendar_date(2015, time::Month::February, 18).expect("valid date");
+        assert_eq!(value.into_attr().s, Some("2015-02-18".to_string()));
+        assert_eq!(Ok(value), Date::from_attr(value.into_attr()));
+        match Date::from_attr("18/02/2015".to_string().into_attr()) {
+            Err(AttributeError::InvalidValue { message }) => {
+                assert!(message.contains("`18/02/2015`"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn time_years_without_four_digits_try_into_attr() {
+        let date = Date::from_calendar_date(-1, time::Month::January, 1).expect("valid date");
+        let date_time = date.midnight();
+        let offset_date_time = date_time.assume_utc();
+        for result in &[
+            date.try_into_attr(),
+            date_time.try_into_attr(),
+            offset_date_time.try_into_attr(),
+        ] {
+            match result {
+                Err(AttributeError::InvalidValue { message }) => {
+                    assert!(message.contains("outside of 0 to 9999"))
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+        // infallible conversions still produce a value
+        assert!(offset_date_time.into_attr().s.is_some());
+        assert!(Date::from_calendar_date(9999, time::Month::December, 31)
+            .expect("valid date")
+            .try_into_attr()
+            .is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn decimal_attr() {
+        for n in &["0.1", "-12.34", "1.500", "1234567890123456.789012345678"] {
+            let value: Decimal = n.parse().expect("valid decimal");
+            assert_eq!(value.into_attr().n.as_deref(), Some(*n));
+            assert_eq!(Ok(value), Decimal::from_attr(value.into_attr()));
+        }
+        assert_eq!(
+            Decimal::from_attr(AttributeValue {
+                n: Some("1.5".into()),
+                ..AttributeValue::default()
+            }),
+            Ok("1.500".parse().unwrap())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn decimal_invalid_attr() {
+        match Decimal::from_attr(AttributeValue {
+            n: Some("0.12345678901234567890123456789012".into()),
+            ..AttributeValue::default()
+        }) {
+            Err(AttributeError::InvalidValue { message }) => {
+                assert!(message.contains("`0.12345678901234567890123456789012`"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            Decimal::from_attr("1.5".to_string().into_attr())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bigdecimal")]
+    fn big_decimal_attr() {
+        for n in &[
+            "0",
+            "-1.5",
+            "12345678901234567890123456789012345678",
+            "0.12345678901234567890123456789012345678",
+            "1E+125",
+            "1E-130",
+            "100000000000000000000000000000000000000000",
+        ] {
+            let value: BigDecimal = n.parse().expect("valid decimal");
+            assert_eq!(Ok(value.clone()), BigDecimal::from_attr(value.into_attr()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bigdecimal")]
+    fn big_decimal_unrepresentable_attr() {
+        for n in &[
+            "123456789012345678901234567890123456789",
+            "-1.23456789012345678901234567890123456789",
+            "1E+126",
+            "1E-131",
+        ] {
+            match BigDecimal::from_attr(AttributeValue {
+                n: Some(n.to_string()),
+                ..AttributeValue::default()
+            }) {
+                Err(AttributeError::InvalidValue { .. }) => (),
+                other => panic!("unexpected result {:?} for {}", other, n),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bigdecimal")]
+    fn big_decimal_unrepresentable_try_into_attr() {
+        let value = "123456789012345678901234567890123456789"
+            .parse::<BigDecimal>()
+            .expect("valid decimal");
+        match value.clone().try_into_attr() {
+            Err(AttributeError::InvalidValue { message }) => {
+                assert!(message.contains("more than the 38 supported"))
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(
+            value.into_attr().n,
+            Some("123456789012345678901234567890123456789".to_string())
+        );
+        assert!("1.5"
+            .parse::<BigDecimal>()
+            .expect("valid decimal")
+            .try_into_attr()
+            .is_ok());
+    }
+
     #[test]
     fn option_some_attr() {
         let value = Some(1);
@@ -885,6 +2147,13 @@ mod test {
         );
     }
 
+    #[test]
+    fn box_attr() {
+        let value = Box::new(1);
+        assert_eq!(1.into_attr(), value.clone().into_attr());
+        assert_eq!(Ok(value), Attribute::from_attr(1.into_attr()));
+    }
+
     #[test]
     fn bool_attr() {
         let value = true;
@@ -903,6 +2172,29 @@ mod test {
         assert_eq!(Ok(value.clone()), Bytes::from_attr(value.into_attr()));
     }
 
+    #[test]
+    fn bytes_attr_does_not_copy() {
+        let value = Bytes::from(vec![0_u8; 1024 * 1024]);
+        let ptr = value.as_ptr();
+        let attr = value.into_attr();
+        assert_eq!(attr.b.as_ref().map(|b| b.as_ptr()), Some(ptr));
+        assert_eq!(Bytes::from_attr(attr).map(|b| b.as_ptr()), Ok(ptr));
+    }
+
+    #[test]
+    fn bytes_mut_attr() {
+        let mut value = BytesMut::with_capacity(4);
+        value.extend_from_slice(b"test");
+        let ptr = value.as_ptr();
+        let attr = value.into_attr();
+        assert_eq!(attr.b.as_ref().map(|b| b.as_ptr()), Some(ptr));
+        assert_eq!(Ok(BytesMut::from(&b"test"[..])), BytesMut::from_attr(attr));
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            BytesMut::from_attr(1.into_attr())
+        );
+    }
+
     #[test]
     fn byte_vec_attr_from_attr() {
         let value = b"test".to_vec();
@@ -925,6 +2217,132 @@ mod test {
         );
     }
 
+    #[test]
+    fn wide_numeric_attr() {
+        let value = 12_345_678_901_234_567_890_123_456_789_012_345_678_u128;
+        assert_eq!(
+            value.into_attr().n,
+            Some("12345678901234567890123456789012345678".to_string())
+        );
+        assert_eq!(Ok(value), u128::from_attr(value.into_attr()));
+        assert_eq!(Ok(i128::MIN), i128::from_attr(i128::MIN.into_attr()));
+        for n in &[
+            "-1",
+            "1.5",
+            "abc",
+            "340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(
+                Err(AttributeError::InvalidFormat),
+                u128::from_attr(AttributeValue {
+                    n: Some(n.to_string()),
+                    ..AttributeValue::default()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn non_zero_attr() {
+        let value = NonZeroU64::new(42);
+        assert_eq!(value.into_attr().n, Some("42".to_string()));
+        assert_eq!(
+            Ok(value),
+            Option::<NonZeroU64>::from_attr(value.into_attr())
+        );
+        assert_eq!(
+            Ok(None),
+            Option::<NonZeroU64>::from_attr(None::<NonZeroU64>.into_attr())
+        );
+        assert_eq!(
+            Ok(NonZeroI8::new(-1).unwrap()),
+            NonZeroI8::from_attr((-1).into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            NonZeroU64::from_attr(0.into_attr())
+        );
+    }
+
+    #[test]
+    fn non_finite_float_try_into_attr() {
+        assert_eq!(1.5_f64.try_into_attr(), Ok(1.5_f64.into_attr()));
+        for value in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            match value.try_into_attr() {
+                Err(AttributeError::InvalidValue { .. }) => (),
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+        assert!(Some(f32::NAN).try_into_attr().is_err());
+        assert!(vec![1.0, f64::INFINITY].try_into_attr().is_err());
+        assert_eq!(
+            None::<f64>.try_into_attr(),
+            Ok(AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            })
+        );
+    }
+
+    #[test]
+    fn address_attr() {
+        for address in &["192.168.0.1", "::1", "fe80::1", "::ffff:192.168.0.1"] {
+            let value: IpAddr = address.parse().expect("valid address");
+            assert_eq!(value.into_attr().s.as_deref(), Some(*address));
+            assert_eq!(Ok(value), IpAddr::from_attr(value.into_attr()));
+        }
+        for address in &[
+            "192.168.0.1:80",
+            "[fe80::1%2]:443",
+            "[::ffff:10.0.0.1]:8080",
+        ] {
+            let value: SocketAddr = address.parse().expect("valid address");
+            assert_eq!(value.into_attr().s.as_deref(), Some(*address));
+            assert_eq!(Ok(value), SocketAddr::from_attr(value.into_attr()));
+        }
+        match Ipv4Addr::from_attr("::1".to_string().into_attr()) {
+            Err(AttributeError::InvalidValue { message }) => assert!(message.contains("`::1`")),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            Ipv6Addr::from_attr(1.into_attr())
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn ip_addresses_roundtrip(value in proptest::prelude::any::<IpAddr>()) {
+            proptest::prop_assert_eq!(Ok(value), IpAddr::from_attr(value.into_attr()));
+        }
+    }
+
+    #[test]
+    fn path_attr() {
+        let value = PathBuf::from("/var/log/syslog");
+        assert_eq!(
+            value.clone().into_attr().s,
+            Some("/var/log/syslog".to_string())
+        );
+        assert_eq!(Ok(value.clone()), PathBuf::from_attr(value.into_attr()));
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            PathBuf::from_attr(1.into_attr())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn non_utf8_path_attr() {
+        use std::os::unix::ffi::OsStringExt;
+        let value = PathBuf::from(OsString::from_vec(vec![b'/', 0xff, b'a']));
+        let attr = value.clone().into_attr();
+        assert_eq!(attr.s, None);
+        assert_eq!(attr.b.as_deref(), Some(&[b'/', 0xff, b'a'][..]));
+        assert_eq!(Ok(value), PathBuf::from_attr(attr));
+    }
+
     #[test]
     fn string_into_attr() {
         assert_eq!(
@@ -1111,4 +2529,125 @@ mod test {
             Ok(btreemap! { "foo".to_string() => 1 })
         );
     }
+
+    macro_rules! tuple_roundtrips {
+        ($($test:ident: $value:expr,)+) => {
+            $(
+                #[test]
+                fn $test() {
+                    fn roundtrip<T: Attribute + Clone + PartialEq + std::fmt::Debug>(value: T) {
+                        assert_eq!(Ok(value.clone()), T::from_attr(value.into_attr()));
+                    }
+                    roundtrip($value);
+                }
+            )+
+        };
+    }
+
+    tuple_roundtrips! {
+        unary_tuple_attr: ("a".to_string(),),
+        pair_tuple_attr: (1.5_f64, -2.5_f64),
+        mixed_tuple_attr: ("a".to_string(), 1_u32, true),
+        optional_tuple_attr: (Some(1_i64), None::<String>, Some("b".to_string())),
+        nested_tuple_attr: (("a".to_string(), 1_u16), (None::<bool>, (2_i32,))),
+        widest_tuple_attr: (
+            1_u32, 2_u32, 3_u32, 4_u32, 5_u32, 6_u32,
+            7_u32, 8_u32, 9_u32, 10_u32, 11_u32, "12".to_string(),
+        ),
+    }
+
+    #[test]
+    fn tuple_attr_is_positional_list() {
+        assert_eq!(
+            serde_json::to_string(&("a".to_string(), 1).into_attr()).unwrap(),
+            r#"{"L":[{"S":"a"},{"N":"1"}]}"#
+        );
+    }
+
+    #[test]
+    fn tuple_attr_rejects_other_lengths() {
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            <(u32, u32)>::from_attr(vec![1_u32].into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            <(u32, u32)>::from_attr(vec![1_u32, 2, 3].into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            <(u32, u32)>::from_attr(1.into_attr())
+        );
+    }
+
+    #[test]
+    fn array_attr() {
+        let value = [1.5_f32, -2.0, 0.25];
+        assert_eq!(
+            serde_json::to_string(&value.into_attr()).unwrap(),
+            r#"{"L":[{"N":"1.5"},{"N":"-2"},{"N":"0.25"}]}"#
+        );
+        assert_eq!(Ok(value), <[f32; 3]>::from_attr(value.into_attr()));
+        assert_eq!(
+            Ok([Some("a".to_string()), None]),
+            <[Option<String>; 2]>::from_attr([Some("a".to_string()), None].into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            <[f32; 3]>::from_attr(vec![1.5_f32, 2.0].into_attr())
+        );
+    }
+
+    #[test]
+    fn byte_array_attr() {
+        let value = [0xde_u8, 0xad, 0xbe, 0xef];
+        assert_eq!(
+            value.into_attr().b,
+            Some(vec![0xde, 0xad, 0xbe, 0xef].into())
+        );
+        assert_eq!(Ok(value), <[u8; 4]>::from_attr(value.into_attr()));
+        assert_eq!(
+            Err(AttributeError::InvalidFormat),
+            <[u8; 16]>::from_attr(value.into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            <[u8; 4]>::from_attr(vec![1_u32].into_attr())
+        );
+    }
+
+    #[test]
+    fn shared_attr() {
+        let value = Arc::new(vec!["a".to_string()]);
+        let shared = Arc::clone(&value);
+        assert_eq!(
+            Ok(value.clone()),
+            Arc::<Vec<String>>::from_attr(shared.into_attr())
+        );
+        assert_eq!(
+            Ok(Rc::new(1_u32)),
+            Rc::<u32>::from_attr(Rc::new(1_u32).into_attr())
+        );
+    }
+
+    #[test]
+    fn shared_str_attr() {
+        let value: Arc<str> = Arc::from("shared");
+        assert_eq!(value.clone().into_attr().s, Some("shared".to_string()));
+        assert_eq!(
+            Ok(value),
+            Arc::<str>::from_attr("shared".to_string().into_attr())
+        );
+        assert_eq!(
+            Err(AttributeError::InvalidType),
+            Rc::<str>::from_attr(1.into_attr())
+        );
+    }
+
+    #[test]
+    fn shared_bytes_attr() {
+        let value: Rc<[u8]> = Rc::from(&[1_u8, 2, 3][..]);
+        assert_eq!(value.clone().into_attr().b, Some(vec![1, 2, 3].into()));
+        assert_eq!(Ok(value.clone()), Rc::<[u8]>::from_attr(value.into_attr()));
+    }
 }
@@ -0,0 +1,66 @@
+//! Support for testing that items round-trip through their attributes
+//!
+//! Types deriving `Item` or `Attributes` convert into and out of [Attributes](../type.Attributes.html).
+//! [check](fn.check.html) asserts that such a conversion returns an equal value and may be used
+//! within [proptest](https://crates.io/crates/proptest) tests, while [assert](fn.assert.html)
+//! panics instead. Types implementing proptest's `Arbitrary`, for instance through
+//! [proptest-derive](https://crates.io/crates/proptest-derive), may be checked against
+//! generated values with [arbitrary](fn.arbitrary.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::{roundtrip, Item};
+//!
+//! #[derive(Item, Clone, Debug, PartialEq)]
+//! struct Person {
+//!     #[dynomite(partition_key)]
+//!     id: String,
+//!     age: u32,
+//! }
+//!
+//! roundtrip::assert(Person {
+//!     id: "123".into(),
+//!     age: 42,
+//! });
+//! ```
+use crate::{Attributes, FromAttributes};
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    prop_assert_eq,
+    test_runner::{TestCaseError, TestRunner},
+};
+use std::fmt::Debug;
+
+/// Fails if a value's attributes do not convert back into an equal value
+pub fn check<T>(value: T) -> Result<(), TestCaseError>
+where
+    T: Clone + Debug + PartialEq + FromAttributes + Into<Attributes>,
+{
+    let attrs: Attributes = value.clone().into();
+    let parsed = T::from_attrs(attrs)
+        .map_err(|err| TestCaseError::fail(format!("{:?} failed to round-trip: {}", value, err)))?;
+    prop_assert_eq!(parsed, value);
+    Ok(())
+}
+
+/// Panics if a value's attributes do not convert back into an equal value
+pub fn assert<T>(value: T)
+where
+    T: Clone + Debug + PartialEq + FromAttributes + Into<Attributes>,
+{
+    if let Err(err) = check(value) {
+        panic!("{}", err)
+    }
+}
+
+/// Panics if the attributes of any value generated by a type's `Arbitrary` strategy do not
+/// convert back into an equal value, reporting the simplest failing value
+pub fn arbitrary<T>()
+where
+    T: Arbitrary + Clone + PartialEq + FromAttributes + Into<Attributes>,
+{
+    if let Err(err) = TestRunner::default().run(&any::<T>(), check::<T>) {
+        panic!("{}", err)
+    }
+}
@@ -0,0 +1,141 @@
+//! Table key schema metadata
+//!
+//! Items derived with `#[derive(Item)]` provide a `schema()` function describing the
+//! key attributes of their table, so that infrastructure code creating or validating
+//! tables need not repeat them. Each element converts into its
+//! [rusoto_dynamodb](https://docs.rs/rusoto_dynamodb) counterpart.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::schema::{AttributeDefinition, KeySchemaElement, KeyType, ScalarType, Schema};
+//!
+//! let schema = Schema {
+//!     key_schema: vec![KeySchemaElement {
+//!         attribute_name: "id".into(),
+//!         key_type: KeyType::Hash,
+//!     }],
+//!     attribute_definitions: vec![AttributeDefinition {
+//!         attribute_name: "id".into(),
+//!         attribute_type: ScalarType::S,
+//!     }],
+//! };
+//! let key_schema = schema
+//!     .key_schema
+//!     .into_iter()
+//!     .map(dynomite::dynamodb::KeySchemaElement::from)
+//!     .collect::<Vec<_>>();
+//! assert_eq!(key_schema[0].key_type, "HASH");
+//! ```
+use crate::dynamodb;
+
+/// The role of a key attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// A partition key
+    Hash,
+    /// A sort key
+    Range,
+}
+
+impl KeyType {
+    /// Returns the name DynamoDB uses for this key type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyType::Hash => "HASH",
+            KeyType::Range => "RANGE",
+        }
+    }
+}
+
+/// The scalar type of a key attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScalarType {
+    /// A string
+    S,
+    /// A number
+    N,
+    /// Binary data
+    B,
+}
+
+impl ScalarType {
+    /// Returns the name DynamoDB uses for this scalar type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScalarType::S => "S",
+            ScalarType::N => "N",
+            ScalarType::B => "B",
+        }
+    }
+}
+
+/// An attribute of a table's key schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySchemaElement {
+    /// The name of the key attribute
+    pub attribute_name: String,
+    /// The role of the key attribute
+    pub key_type: KeyType,
+}
+
+impl From<KeySchemaElement> for dynamodb::KeySchemaElement {
+    fn from(element: KeySchemaElement) -> Self {
+        dynamodb::KeySchemaElement {
+            attribute_name: element.attribute_name,
+            key_type: element.key_type.as_str().into(),
+        }
+    }
+}
+
+/// The definition of a key attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeDefinition {
+    /// The name of the key attribute
+    pub attribute_name: String,
+    /// The scalar type of the key attribute
+    pub attribute_type: ScalarType,
+}
+
+impl From<AttributeDefinition> for dynamodb::AttributeDefinition {
+    fn from(definition: AttributeDefinition) -> Self {
+        dynamodb::AttributeDefinition {
+            attribute_name: definition.attribute_name,
+            attribute_type: definition.attribute_type.as_str().into(),
+        }
+    }
+}
+
+/// The key schema and key attribute definitions of a table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    /// The partition key followed by the sort key, if any
+    pub key_schema: Vec<KeySchemaElement>,
+    /// The scalar types of each key attribute
+    pub attribute_definitions: Vec<AttributeDefinition>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_schema_elements_convert() {
+        let element = dynamodb::KeySchemaElement::from(KeySchemaElement {
+            attribute_name: "sk".into(),
+            key_type: KeyType::Range,
+        });
+        assert_eq!(element.attribute_name, "sk");
+        assert_eq!(element.key_type, "RANGE");
+    }
+
+    #[test]
+    fn attribute_definitions_convert() {
+        let definition = dynamodb::AttributeDefinition::from(AttributeDefinition {
+            attribute_name: "id".into(),
+            attribute_type: ScalarType::N,
+        });
+        assert_eq!(definition.attribute_name, "id");
+        assert_eq!(definition.attribute_type, "N");
+    }
+}
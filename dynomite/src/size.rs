@@ -0,0 +1,165 @@
+//! Item size estimation
+//!
+//! DynamoDB limits items to 400KB and bills reads and writes by item size.
+//! These functions approximate sizes following DynamoDB's
+//! [documented rules](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/CapacityUnitCalculations.html)
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::{attr_map, size};
+//!
+//! let item = attr_map! {
+//!     "id" => "abc".to_string()
+//! };
+//! assert_eq!(size::item(&item), 5);
+//! ```
+use crate::{AttributeValue, Attributes};
+
+/// The maximum size of a DynamoDB item in bytes
+pub const MAX_ITEM_SIZE: usize = 400 * 1024;
+
+// each list or map requires 3 bytes of overhead,
+// plus 1 byte for each of its elements
+const COLLECTION_OVERHEAD: usize = 3;
+const ELEMENT_OVERHEAD: usize = 1;
+
+/// Returns the approximate size in bytes of an item's attributes
+pub fn item(attrs: &Attributes) -> usize {
+    attrs
+        .iter()
+        .map(|(name, value)| name.len() + attribute(value))
+        .sum()
+}
+
+/// Returns the approximate size in bytes of an attribute value, excluding its name
+pub fn attribute(value: &AttributeValue) -> usize {
+    if let Some(s) = &value.s {
+        return s.len();
+    }
+    if let Some(n) = &value.n {
+        return number(n);
+    }
+    if let Some(b) = &value.b {
+        return b.len();
+    }
+    if value.bool.is_some() || value.null.is_some() {
+        return 1;
+    }
+    if let Some(ss) = &value.ss {
+        return ss.iter().map(String::len).sum();
+    }
+    if let Some(ns) = &value.ns {
+        return ns.iter().map(|n| number(n)).sum();
+    }
+    if let Some(bs) = &value.bs {
+        return bs.iter().map(|b| b.len()).sum();
+    }
+    if let Some(l) = &value.l {
+        return COLLECTION_OVERHEAD
+            + l.iter()
+                .map(|value| attribute(value) + ELEMENT_OVERHEAD)
+                .sum::<usize>();
+    }
+    if let Some(m) = &value.m {
+        return COLLECTION_OVERHEAD
+            + m.iter()
+                .map(|(name, value)| name.len() + attribute(value) + ELEMENT_OVERHEAD)
+                .sum::<usize>();
+    }
+    0
+}
+
+/// Numbers require 1 byte per two significant digits, plus 1 byte,
+/// plus 1 byte for negative numbers
+fn number(n: &str) -> usize {
+    let mantissa = n.split(|c| c == 'e' || c == 'E').next().unwrap_or_default();
+    let digits = mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    let significant = digits.trim_start_matches('0').trim_end_matches('0').len();
+    let sign = if mantissa.starts_with('-') { 1 } else { 0 };
+    (significant + 1) / 2 + 1 + sign
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attribute;
+
+    #[test]
+    fn strings_are_their_utf8_length() {
+        assert_eq!(attribute(&"abc".to_string().into_attr()), 3);
+        assert_eq!(attribute(&"é".to_string().into_attr()), 2);
+    }
+
+    #[test]
+    fn numbers_are_sized_by_significant_digits() {
+        assert_eq!(number("0"), 1);
+        assert_eq!(number("1"), 2);
+        assert_eq!(number("12"), 2);
+        assert_eq!(number("123"), 3);
+        assert_eq!(number("1000"), 2);
+        assert_eq!(number("0.0012"), 2);
+        assert_eq!(number("-123"), 4);
+        assert_eq!(number("12345678901234567890"), 11);
+    }
+
+    #[test]
+    fn scalars() {
+        assert_eq!(attribute(&true.into_attr()), 1);
+        assert_eq!(attribute(&None::<String>.into_attr()), 1);
+        assert_eq!(attribute(&vec![1_u8, 2, 3].into_attr()), 3);
+    }
+
+    #[test]
+    fn collections_include_overhead() {
+        assert_eq!(attribute(&Vec::<String>::new().into_attr()), 3);
+        assert_eq!(
+            attribute(&vec!["a".to_string(), "b".to_string()].into_attr()),
+            3 + 2 * (1 + 1)
+        );
+        let mut map = std::collections::HashMap::new();
+        map.insert("k".to_string(), true);
+        assert_eq!(attribute(&map.into_attr()), 3 + 1 + 1 + 1);
+    }
+
+    #[test]
+    fn items_include_attribute_names() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "abc".to_string().into_attr());
+        attrs.insert("count".into(), 123.into_attr());
+        assert_eq!(item(&attrs), 2 + 3 + 5 + 3);
+    }
+
+    #[test]
+    fn mixed_item() {
+        let mut attrs = Attributes::new();
+        // 2 + 9
+        attrs.insert("id".into(), "user#1234".to_string().into_attr());
+        // 5 + 3 significant digits in 2 bytes + 1 + 1 for the sign
+        attrs.insert(
+            "score".into(),
+            AttributeValue {
+                n: Some("-12.50".into()),
+                ..AttributeValue::default()
+            },
+        );
+        // 4 + 1 + 2
+        attrs.insert(
+            "tags".into(),
+            AttributeValue {
+                ss: Some(vec!["a".into(), "bc".into()]),
+                ..AttributeValue::default()
+            },
+        );
+        // 6 + 1
+        attrs.insert("active".into(), true.into_attr());
+        // 4 + 3 + 1 + 1 + 1
+        let mut meta = std::collections::HashMap::new();
+        meta.insert("k".to_string(), "v".to_string());
+        attrs.insert("meta".into(), meta.into_attr());
+        assert_eq!(item(&attrs), 11 + 9 + 7 + 7 + 10);
+    }
+}
@@ -0,0 +1,157 @@
+//! Support for auto-managed timestamp fields
+//!
+//! Fields marked with `#[dynomite(created_at)]` or `#[dynomite(updated_at)]`
+//! are assigned the current time, as reported by this module's clock, when an item
+//! is converted into its `Attributes`. Tests may replace the clock with
+//! [with_clock](fn.with_clock.html) for deterministic results.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::timestamp::{self, Timestamp};
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! fn fixed() -> SystemTime {
+//!     UNIX_EPOCH + Duration::from_secs(1_000)
+//! }
+//!
+//! timestamp::with_clock(fixed, || {
+//!     assert_eq!(SystemTime::now_timestamp(), fixed());
+//! });
+//! ```
+#[cfg(feature = "chrono")]
+use chrono::{
+    offset::{FixedOffset, Local},
+    DateTime, Utc,
+};
+use std::{
+    cell::Cell,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+thread_local! {
+    static CLOCK: Cell<fn() -> SystemTime> = Cell::new(SystemTime::now);
+}
+
+/// Returns the current time as reported by the current thread's clock
+pub fn now() -> SystemTime {
+    CLOCK.with(|clock| clock.get()())
+}
+
+/// Replaces the current thread's clock with `clock` for the duration of `f`
+pub fn with_clock<F, R>(
+    clock: fn() -> SystemTime,
+    f: F,
+) -> R
+where
+    F: FnOnce() -> R,
+{
+    // restores the previous clock even if `f` panics
+    struct Restore(fn() -> SystemTime);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0;
+            CLOCK.with(|clock| clock.set(previous));
+        }
+    }
+    let _restore = Restore(CLOCK.with(|current| current.replace(clock)));
+    f()
+}
+
+/// A type which may be assigned the current time
+pub trait Timestamp: Sized {
+    /// Returns the current time as reported by this module's clock
+    fn now_timestamp() -> Self;
+
+    /// Returns true if this value has not yet been assigned a time
+    fn is_unset(&self) -> bool;
+}
+
+/// Unset when equal to the `UNIX_EPOCH`
+impl Timestamp for SystemTime {
+    fn now_timestamp() -> Self {
+        now()
+    }
+
+    fn is_unset(&self) -> bool {
+        *self == UNIX_EPOCH
+    }
+}
+
+/// Unset when equal to the `UNIX_EPOCH`
+#[cfg(feature = "chrono")]
+impl Timestamp for DateTime<Utc> {
+    fn now_timestamp() -> Self {
+        now().into()
+    }
+
+    fn is_unset(&self) -> bool {
+        SystemTime::from(*self) == UNIX_EPOCH
+    }
+}
+
+/// Unset when equal to the `UNIX_EPOCH`
+#[cfg(feature = "chrono")]
+impl Timestamp for DateTime<Local> {
+    fn now_timestamp() -> Self {
+        now().into()
+    }
+
+    fn is_unset(&self) -> bool {
+        SystemTime::from(*self) == UNIX_EPOCH
+    }
+}
+
+/// Unset when equal to the `UNIX_EPOCH`
+#[cfg(feature = "chrono")]
+impl Timestamp for DateTime<FixedOffset> {
+    fn now_timestamp() -> Self {
+        DateTime::<Utc>::from(now()).into()
+    }
+
+    fn is_unset(&self) -> bool {
+        SystemTime::from(*self) == UNIX_EPOCH
+    }
+}
+
+/// Unset when `None`
+impl<T: Timestamp> Timestamp for Option<T> {
+    fn now_timestamp() -> Self {
+        Some(T::now_timestamp())
+    }
+
+    fn is_unset(&self) -> bool {
+        self.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn fixed() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_000)
+    }
+
+    #[test]
+    fn with_clock_replaces_and_restores_clock() {
+        assert_ne!(now(), fixed());
+        with_clock(fixed, || assert_eq!(now(), fixed()));
+        assert_ne!(now(), fixed());
+    }
+
+    #[test]
+    fn option_timestamps() {
+        assert!(None::<SystemTime>.is_unset());
+        with_clock(fixed, || {
+            assert_eq!(Option::<SystemTime>::now_timestamp(), Some(fixed()))
+        });
+    }
+
+    #[test]
+    fn epoch_is_unset() {
+        assert!(UNIX_EPOCH.is_unset());
+        assert!(!fixed().is_unset());
+    }
+}
@@ -0,0 +1,178 @@
+//! Support for DynamoDB [time to live](https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/TTL.html) attributes
+//!
+//! DynamoDB expects TTL attributes to be top-level `N` values holding
+//! seconds since the Unix epoch. Fields marked with `#[dynomite(ttl)]` are stored
+//! in this format using [into_attr](fn.into_attr.html) and [from_attr](fn.from_attr.html)
+//! rather than their `Attribute` impl.
+//!
+//! # Examples
+//!
+//! ```
+//! use dynomite::ttl;
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! let expires = UNIX_EPOCH + Duration::from_secs(1_000);
+//! let value = ttl::into_attr(expires);
+//! assert_eq!(value.n, Some("1000".into()));
+//! assert_eq!(ttl::from_attr::<SystemTime>(value), Ok(expires));
+//! ```
+use crate::{AttributeError, AttributeValue};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A type which may be stored as a TTL attribute
+pub trait Ttl: Sized {
+    /// Returns an `N` attribute value holding epoch seconds
+    fn into_ttl(self) -> AttributeValue;
+
+    /// Parses an `N` attribute value holding either integer or fractional epoch seconds
+    fn from_ttl(value: AttributeValue) -> Result<Self, AttributeError>;
+}
+
+/// Values before the Unix epoch are stored as `0`
+impl Ttl for SystemTime {
+    fn into_ttl(self) -> AttributeValue {
+        let seconds = self
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        AttributeValue {
+            n: Some(seconds.to_string()),
+            ..AttributeValue::default()
+        }
+    }
+
+    fn from_ttl(value: AttributeValue) -> Result<Self, AttributeError> {
+        let n = value.n.ok_or(AttributeError::InvalidType)?;
+        let duration = match n.parse::<u64>() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(_) => match n.parse::<f64>() {
+                Ok(seconds) if seconds >= 0.0 && seconds < u64::MAX as f64 => {
+                    Duration::from_secs_f64(seconds)
+                }
+                _ => return Err(AttributeError::InvalidFormat),
+            },
+        };
+        UNIX_EPOCH
+            .checked_add(duration)
+            .ok_or(AttributeError::InvalidFormat)
+    }
+}
+
+/// Values before the Unix epoch are stored as `0`
+#[cfg(feature = "chrono")]
+impl Ttl for DateTime<Utc> {
+    fn into_ttl(self) -> AttributeValue {
+        SystemTime::from(self).into_ttl()
+    }
+
+    fn from_ttl(value: AttributeValue) -> Result<Self, AttributeError> {
+        SystemTime::from_ttl(value).map(Into::into)
+    }
+}
+
+/// `None` is represented by the `NULL` AttributeValue type
+impl<T: Ttl> Ttl for Option<T> {
+    fn into_ttl(self) -> AttributeValue {
+        match self {
+            Some(value) => value.into_ttl(),
+            _ => AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+        }
+    }
+
+    fn from_ttl(value: AttributeValue) -> Result<Self, AttributeError> {
+        match value.null {
+            Some(true) => Ok(None),
+            _ => Ok(Some(T::from_ttl(value)?)),
+        }
+    }
+}
+
+/// Converts a value into an `N` attribute value holding epoch seconds
+pub fn into_attr<T: Ttl>(value: T) -> AttributeValue {
+    value.into_ttl()
+}
+
+/// Parses an `N` attribute value holding integer or fractional epoch seconds
+pub fn from_attr<T: Ttl>(value: AttributeValue) -> Result<T, AttributeError> {
+    T::from_ttl(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(value: &str) -> AttributeValue {
+        AttributeValue {
+            n: Some(value.into()),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn parses_integer_seconds() {
+        assert_eq!(
+            from_attr::<SystemTime>(n("10")),
+            Ok(UNIX_EPOCH + Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn parses_fractional_seconds() {
+        assert_eq!(
+            from_attr::<SystemTime>(n("10.5")),
+            Ok(UNIX_EPOCH + Duration::from_millis(10_500))
+        );
+    }
+
+    #[test]
+    fn rejects_pre_epoch_seconds() {
+        assert_eq!(
+            from_attr::<SystemTime>(n("-10")),
+            Err(AttributeError::InvalidFormat)
+        );
+        assert_eq!(
+            from_attr::<SystemTime>(n("-10.5")),
+            Err(AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn pre_epoch_values_saturate() {
+        assert_eq!(
+            into_attr(UNIX_EPOCH - Duration::from_secs(10)).n,
+            Some("0".into())
+        );
+    }
+
+    #[test]
+    fn truncates_sub_second_precision() {
+        assert_eq!(
+            into_attr(UNIX_EPOCH + Duration::from_millis(10_500)).n,
+            Some("10".into())
+        );
+    }
+
+    #[test]
+    fn rejects_non_numbers() {
+        assert_eq!(
+            from_attr::<SystemTime>(n("soon")),
+            Err(AttributeError::InvalidFormat)
+        );
+        assert_eq!(
+            from_attr::<SystemTime>(AttributeValue::default()),
+            Err(AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    fn none_is_null() {
+        let value = into_attr(None::<SystemTime>);
+        assert_eq!(value.null, Some(true));
+        assert_eq!(from_attr::<Option<SystemTime>>(value), Ok(None));
+    }
+}
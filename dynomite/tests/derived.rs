@@ -1,4 +1,4 @@
-use dynomite_derive::{Attribute, Item};
+use dynomite_derive::{Attribute, Attributes, Item};
 
 #[derive(Item, Default, PartialEq, Debug, Clone)]
 pub struct Author {
@@ -32,51 +32,2745 @@ struct Recipe {
     servings: u64,
 }
 
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Note {
+    #[dynomite(partition_key)]
+    id: String,
+    body: Option<String>,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Customer {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(flatten(prefix = "billing_"))]
+    billing: Address,
+    #[dynomite(flatten(prefix = "shipping_"))]
+    shipping: Address,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Store {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(flatten)]
+    address: Address,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(skip_key_struct)]
+struct Tag {
+    #[dynomite(partition_key)]
+    name: String,
+}
+
+// would collide with a generated key struct
+#[allow(dead_code)]
+struct TagKey;
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(changed_fields, ordered)]
+struct Profile {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(rename = "displayName")]
+    display_name: String,
+    age: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(key_hash)]
+struct Order {
+    #[dynomite(partition_key)]
+    user: String,
+    #[dynomite(sort_key)]
+    id: u64,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+enum Shape {
+    Circle {
+        radius: u32,
+    },
+    Rectangle {
+        #[dynomite(rename = "w")]
+        width: u32,
+        #[dynomite(default)]
+        height: u32,
+    },
+    Empty {},
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(transparent)]
+struct UserId(String);
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(transparent)]
+struct Email {
+    address: String,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(transparent)]
+struct Wrapper<T>(T)
+where
+    T: Clone;
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Account {
+    #[dynomite(partition_key)]
+    id: UserId,
+    email: Email,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(deny_unknown_fields)]
+struct Strict {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(flatten(prefix = "home_"))]
+    home: Address,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Document {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(version, rename = "rev")]
+    version: u64,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Post {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(created_at)]
+    created: Option<std::time::SystemTime>,
+    #[dynomite(updated_at)]
+    updated: std::time::SystemTime,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+enum Status {
+    Open,
+    Closed,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Ticket {
+    #[dynomite(partition_key)]
+    status: Status,
+    #[dynomite(sort_key)]
+    id: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Warehouse {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(map)]
+    address: Address,
+    #[dynomite(flatten(prefix = "loading_"))]
+    loading: Address,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Session {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(ttl)]
+    expires: std::time::SystemTime,
+    #[dynomite(ttl)]
+    idle: Option<std::time::SystemTime>,
+}
+
+#[cfg(feature = "gzip")]
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Blob {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(compress = "gzip")]
+    body: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(derive_default)]
+struct Draft {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(default)]
+    words: u32,
+    tags: Option<Vec<String>>,
+}
+
+#[cfg(feature = "serde_json")]
+#[derive(serde::Serialize, serde::Deserialize, Default, PartialEq, Debug, Clone)]
+struct Settings {
+    theme: String,
+    limits: Vec<u32>,
+}
+
+#[cfg(feature = "serde_json")]
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Preferences {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(json, default, rename = "prefs")]
+    settings: Settings,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Node {
+    #[dynomite(partition_key)]
+    id: String,
+    parent: Option<Box<Node>>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Published;
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Record<S> {
+    #[dynomite(partition_key)]
+    id: String,
+    marker: std::marker::PhantomData<S>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Migrated {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(default, track_presence)]
+    count: u32,
+    #[dynomite(track_presence)]
+    note: Option<String>,
+    #[dynomite(presence)]
+    present: std::collections::HashSet<&'static str>,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(case_insensitive, from_str)]
+enum Standing {
+    Active,
+    Suspended,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(display)]
+enum Region {
+    East,
+    West,
+    #[dynomite(other)]
+    Unknown(String),
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+enum Tier {
+    Free,
+    #[dynomite(other)]
+    Unrecognized,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(update_struct)]
+struct Listing {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(rename = "listingTitle")]
+    title: String,
+    price: u32,
+    note: Option<String>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(sort_key_name = "sk")]
+struct OrderLine {
+    #[dynomite(partition_key)]
+    customer: String,
+    #[dynomite(sort_key, order = 1)]
+    order: u32,
+    #[dynomite(sort_key, order = 0)]
+    kind: String,
+    quantity: u32,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Sku(String);
+
+impl dynomite::Attribute for Sku {
+    fn into_attr(self) -> dynomite::AttributeValue {
+        dynomite::Attribute::into_attr(self.0)
+    }
+    fn from_attr(value: dynomite::AttributeValue) -> Result<Self, dynomite::AttributeError> {
+        dynomite::Attribute::from_attr(value).map(Sku)
+    }
+}
+
+impl From<Sku> for String {
+    fn from(sku: Sku) -> Self {
+        sku.0
+    }
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Product {
+    #[dynomite(partition_key, key_as = "String")]
+    sku: Sku,
+    name: String,
+}
+
+#[derive(Item, serde::Serialize, PartialEq, Debug, Clone)]
+struct Contact {
+    #[serde(rename = "contactId", skip_serializing_if = "String::is_empty")]
+    #[dynomite(partition_key)]
+    id: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Lenient {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(default)]
+    strict: u32,
+    #[dynomite(default_on_error)]
+    lenient: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Digest {
+    #[dynomite(partition_key)]
+    digest: Vec<u8>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct FixedDigest {
+    #[dynomite(partition_key)]
+    digest: [u8; 4],
+}
+
+// a stand in for real encryption, never use this outside of tests
+mod xor {
+    use dynomite::{AttributeError, AttributeValue};
+
+    const KEY: u8 = 0x2a;
+
+    pub fn encrypt(plain: AttributeValue) -> Result<AttributeValue, AttributeError> {
+        let plain = plain.s.ok_or(AttributeError::InvalidType)?;
+        Ok(AttributeValue {
+            b: Some(plain.bytes().map(|b| b ^ KEY).collect::<Vec<_>>().into()),
+            ..AttributeValue::default()
+        })
+    }
+
+    pub fn decrypt(cipher: AttributeValue) -> Result<AttributeValue, AttributeError> {
+        let cipher = cipher.b.ok_or(AttributeError::InvalidType)?;
+        let plain = cipher.iter().map(|b| b ^ KEY).collect::<Vec<_>>();
+        Ok(AttributeValue {
+            s: Some(String::from_utf8(plain).map_err(|_| AttributeError::InvalidFormat)?),
+            ..AttributeValue::default()
+        })
+    }
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Patient {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(encrypt_with = "xor")]
+    ssn: String,
+}
+
+// xor only encrypts strings, failing for anything else
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Prescription {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(encrypt_with = "xor")]
+    dose: u32,
+}
+
+#[cfg(feature = "crud")]
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(crud, table = "people")]
+struct Person {
+    #[dynomite(partition_key)]
+    id: String,
+    name: String,
+}
+
+// generated crud methods are only type checked as no DynamoDB table is available to tests
+#[cfg(feature = "crud")]
+#[allow(dead_code)]
+async fn rename_person<C: dynomite::dynamodb::DynamoDb>(
+    client: &C,
+    id: String,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    match Person::get(client, PersonKey { id }).await? {
+        Some(mut person) => {
+            person.name = "renamed".into();
+            person.save(client).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(feature = "crud")]
+#[allow(dead_code)]
+async fn delete_person(
+    client: &dyn dynomite::dynamodb::DynamoDb,
+    person: &Person,
+) -> Result<(), dynomite::crud::CrudError<dynomite::dynamodb::DeleteItemError>> {
+    person.delete(client).await
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Thread {
+    #[dynomite(partition_key)]
+    forum: String,
+    #[dynomite(sort_key)]
+    subject: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Migrating {
+    #[dynomite(partition_key, rename = "newId", key_rename = "oldId")]
+    id: String,
+    #[dynomite(sort_key)]
+    #[dynomite(key_rename = "legacyVersion")]
+    version: u32,
+}
+
+// stands in for a facade crate re-exporting dynomite
+mod platform {
+    pub use ::dynomite;
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(crate = "crate::platform::dynomite")]
+struct Facade {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(sort_key)]
+    kind: FacadeKind,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(crate = "crate::platform::dynomite")]
+enum FacadeKind {
+    Internal,
+    External,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct PrimaryKey {
+    #[dynomite(partition_key)]
+    pk: String,
+    #[dynomite(sort_key)]
+    sk: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Entity {
+    #[dynomite(flatten, key)]
+    key: PrimaryKey,
+    name: String,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Shipping(#[dynomite(flatten)] Address);
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(prefix = "STATUS#", display, as_str)]
+enum Stage {
+    #[dynomite(rename = "active")]
+    Active,
+    #[dynomite(rename = "closed")]
+    Closed,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(prefix = "PRIORITY#", lenient)]
+enum Priority {
+    High,
+    Low,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+#[dynomite(tag = "kind")]
+enum Payment {
+    Card { last4: String },
+    Cash {},
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+#[dynomite(tag = "kind", content = "data")]
+enum Notification {
+    Email { address: String },
+    Sms { number: String },
+}
+
+#[derive(Attributes, Debug, Clone, PartialEq)]
+struct TenantKey {
+    #[dynomite(rename = "pk")]
+    tenant: String,
+    #[dynomite(rename = "sk")]
+    id: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(key_struct = "TenantKey")]
+struct Invoice {
+    #[dynomite(partition_key, rename = "pk")]
+    tenant: String,
+    #[dynomite(sort_key, rename = "sk")]
+    id: String,
+    total: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(key_struct = "TenantKey")]
+struct Receipt {
+    #[dynomite(partition_key, rename = "pk")]
+    tenant: String,
+    #[dynomite(sort_key, rename = "sk")]
+    id: String,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Member {
+    #[dynomite(alias = "userName", alias = "username")]
+    user_name: String,
+    #[dynomite(default, alias = "emailAddress")]
+    email: String,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Handle {
+    #[dynomite(rename = "newName", alias = "oldName", alias = "legacyName")]
+    name: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(update_struct, changed_fields, derive_default)]
+struct Audited {
+    #[dynomite(partition_key)]
+    id: String,
+    #[cfg(feature = "gzip")]
+    audit_log: Vec<String>,
+    #[cfg(not(feature = "gzip"))]
+    #[dynomite(default)]
+    revision: u32,
+}
+
+#[cfg(feature = "chrono")]
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Measurement {
+    taken: chrono::DateTime<chrono::Utc>,
+    #[dynomite(epoch)]
+    recorded: chrono::DateTime<chrono::Utc>,
+    #[dynomite(epoch)]
+    verified: Option<std::time::SystemTime>,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Coordinates {
+    #[dynomite(rename = "lat")]
+    latitude: u32,
+    longitude: u32,
+    #[dynomite(flatten(prefix = "alt_"))]
+    altitude: Altitude,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Altitude {
+    meters: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(key_delimiter = "/")]
+struct Visit {
+    #[dynomite(partition_key)]
+    person: String,
+    #[dynomite(sort_key)]
+    day: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Archived {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(read_only, rename = "legacyName")]
+    legacy_name: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Movie {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(flatten(prefix = "title_"))]
+    titles: std::collections::HashMap<String, String>,
+    year: u32,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(rename_all = "camelCase")]
+struct Subscriber {
+    #[dynomite(partition_key)]
+    email_address: String,
+    #[dynomite(sort_key, rename = "plan")]
+    plan_name: String,
+    display_name: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(derive_default)]
+struct UserDefaults {
+    #[dynomite(partition_key)]
+    user: String,
+    #[dynomite(default_value = 42)]
+    page_size: u32,
+    #[dynomite(default_value = true)]
+    notifications: bool,
+    #[dynomite(default_value = 1.5)]
+    zoom: f64,
+    #[dynomite(default_value = "unknown", default_on_error)]
+    theme: String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(merge)]
+struct CachedProfile {
+    #[dynomite(partition_key)]
+    id: String,
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Reading {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(lenient_number)]
+    value: f64,
+    #[dynomite(lenient_number)]
+    count: Option<u32>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(table = "subscriptions")]
+struct Subscription {
+    #[dynomite(partition_key)]
+    id: String,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(as_str)]
+enum Availability {
+    #[dynomite(rename = "A")]
+    InStock,
+    #[dynomite(rename = "B")]
+    Backordered,
+    #[dynomite(rename = "D")]
+    Discontinued,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(deny_unknown_fields)]
+struct SearchEntry {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(skip_serializing)]
+    legacy_rank: u32,
+    #[dynomite(skip_deserializing, rename = "searchTerms")]
+    search_terms: Vec<String>,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Snowflake {
+    #[dynomite(partition_key)]
+    id: u128,
+    #[dynomite(sort_key)]
+    sequence: i128,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Station {
+    #[dynomite(partition_key)]
+    id: String,
+    temperature: f64,
+    location: GeoPoint,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct GeoPoint {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Attribute, PartialEq, Debug, Clone)]
+#[dynomite(transparent)]
+struct Celsius(f64);
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Beacon {
+    reading: Celsius,
+    #[dynomite(flatten)]
+    position: GeoPoint,
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+struct Position(#[dynomite(flatten)] GeoPoint);
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+enum Area {
+    Circle { radius: f64 },
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+#[dynomite(
+    rename_all_serialize = "camelCase",
+    rename_all_deserialize = "snake_case"
+)]
+struct MigratingContact {
+    phone_number: String,
+    first_name: String,
+}
+
+/// Attributes nested within an event envelope's `detail` map
+#[derive(Attributes, Debug, Clone, PartialEq)]
+#[dynomite(wrapped = "detail")]
+struct Shipment {
+    tracking_number: String,
+    #[dynomite(default)]
+    parcels: u32,
+}
+
+#[derive(Attributes, Debug, Clone, PartialEq)]
+struct NullableReading {
+    sensor: String,
+    #[dynomite(null_when_default)]
+    unit: String,
+    #[dynomite(null_when_default, default)]
+    value: u32,
+}
+
+// deliberately not Clone, to check approximate_size borrows the item
+#[derive(Item, PartialEq, Debug)]
+struct Consignment {
+    #[dynomite(partition_key)]
+    id: String,
+    weight: f64,
+    fragile: bool,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(skip_query_struct)]
+struct Topic {
+    #[dynomite(partition_key)]
+    forum: String,
+    #[dynomite(sort_key)]
+    title: String,
+}
+
+// would collide with a generated query builder
+#[allow(dead_code)]
+struct TopicQuery;
+
+#[derive(Item, PartialEq, Debug, Clone)]
+struct Reply {
+    #[dynomite(partition_key)]
+    topic: String,
+    #[dynomite(sort_key)]
+    body: std::string::String,
+}
+
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(skip_scan_filters)]
+struct Memo {
+    #[dynomite(partition_key)]
+    id: String,
+    title: String,
+}
+
+// would collide with a generated scan filter
+impl Memo {
+    fn title_eq(
+        &self,
+        title: &str,
+    ) -> bool {
+        self.title == title
+    }
+}
+
+#[derive(Attributes, PartialEq, Debug, Clone)]
+#[dynomite(
+    rename_all_serialize = "camelCase",
+    rename_all_deserialize = "snake_case"
+)]
+struct MigratingSupplier {
+    company_name: String,
+    #[dynomite(flatten)]
+    address: Address,
+}
+
+// omits its encrypted field from scan filters and updates, which could only panic
+#[derive(Item, PartialEq, Debug, Clone)]
+#[dynomite(update_struct)]
+struct Refill {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(encrypt_with = "xor")]
+    dose: u32,
+    repeats: u32,
+}
+
+// would collide with a generated scan filter
+impl Refill {
+    fn dose_eq(
+        &self,
+        dose: u32,
+    ) -> bool {
+        self.dose == dose
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::*;
-    use dynomite::{Attribute, Attributes, FromAttributes, Item};
+    use super::*;
+    use dynomite::{dynamodb::AttributeValue, Attribute, Attributes, FromAttributes, Item};
+
+    #[test]
+    fn derived_key() {
+        let value = Recipe {
+            id: "test".into(),
+            servings: 1,
+        };
+        assert_eq!(value.key(), RecipeKey { id: "test".into() }.into());
+    }
+
+    #[test]
+    fn to_and_from_book() {
+        let value = Book {
+            title: "rust".into(),
+            ..Default::default()
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(value, Book::from_attrs(attrs).unwrap())
+    }
+
+    #[test]
+    fn derive_attr() {
+        #[derive(Attribute, Debug, PartialEq)]
+        enum Foo {
+            Bar,
+        };
+        assert_eq!(Foo::Bar, Foo::from_attr(Foo::Bar.into_attr()).unwrap());
+    }
+
+    #[test]
+    fn field_rename() {
+        let value = Recipe {
+            id: "aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee".into(),
+            servings: 2,
+        };
+
+        let attrs: Attributes = value.clone().into();
+        assert!(attrs.contains_key("RecipeId"));
+        assert!(!attrs.contains_key("id"));
+
+        assert_eq!(value, Recipe::from_attrs(attrs).unwrap());
+    }
+
+    #[test]
+    fn option_field_present() {
+        let value = Note {
+            id: "test".into(),
+            body: Some("body".into()),
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(value, Note::from_attrs(attrs).unwrap());
+    }
+
+    #[test]
+    fn option_field_null() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        attrs.insert(
+            "body".into(),
+            AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+        );
+        assert_eq!(
+            Note {
+                id: "test".into(),
+                body: None
+            },
+            Note::from_attrs(attrs).unwrap()
+        );
+    }
+
+    #[test]
+    fn option_field_absent() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        assert_eq!(
+            Note {
+                id: "test".into(),
+                body: None
+            },
+            Note::from_attrs(attrs).unwrap()
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        let value = Store {
+            id: "test".into(),
+            address: Address {
+                street: "1 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs.len(), 3);
+        assert!(attrs.contains_key("street"));
+        assert!(attrs.contains_key("city"));
+        assert_eq!(value, Store::from_attrs(attrs).unwrap());
+    }
+
+    #[test]
+    fn flatten_with_prefix() {
+        let value = Customer {
+            id: "test".into(),
+            billing: Address {
+                street: "1 Main St".into(),
+                city: "Springfield".into(),
+            },
+            shipping: Address {
+                street: "2 Side St".into(),
+                city: "Shelbyville".into(),
+            },
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs.len(), 5);
+        for name in &[
+            "billing_street",
+            "billing_city",
+            "shipping_street",
+            "shipping_city",
+        ] {
+            assert!(attrs.contains_key(*name));
+        }
+        assert_eq!(value, Customer::from_attrs(attrs).unwrap());
+    }
+
+    #[test]
+    fn skip_key_struct() {
+        let value = Tag {
+            name: "test".into(),
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(value.key(), attrs);
+        assert_eq!(value, Tag::from_attrs(attrs).unwrap());
+    }
+
+    #[test]
+    fn changed_fields() {
+        let value = Profile {
+            id: "test".into(),
+            display_name: "before".into(),
+            age: 1,
+        };
+        assert!(value.changed_fields(&value.clone()).is_empty());
+        let other = Profile {
+            display_name: "after".into(),
+            ..value.clone()
+        };
+        assert_eq!(value.changed_fields(&other), vec!["displayName"]);
+    }
+
+    #[test]
+    fn key_hash() {
+        use std::collections::HashSet;
+        let mut keys = HashSet::new();
+        keys.insert(OrderKey {
+            user: "test".into(),
+            id: 1,
+        });
+        keys.insert(OrderKey {
+            user: "test".into(),
+            id: 1,
+        });
+        assert_eq!(keys.len(), 1);
+    }
+
+    #[test]
+    fn enum_variants() {
+        let value = Shape::Rectangle {
+            width: 2,
+            height: 3,
+        };
+        let attrs: Attributes = value.clone().into();
+        let fields = attrs["Rectangle"].m.as_ref().expect("expected map");
+        assert!(fields.contains_key("w"));
+        assert_eq!(Shape::from_attrs(attrs), Ok(value));
+
+        let value = Shape::Circle { radius: 1 };
+        assert_eq!(Shape::from_attr(value.clone().into_attr()), Ok(value));
+
+        let value = Shape::Empty {};
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(Shape::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn enum_variant_defaults() {
+        let mut fields = Attributes::new();
+        fields.insert("w".into(), 2_u32.into_attr());
+        let mut attrs = Attributes::new();
+        attrs.insert(
+            "Rectangle".into(),
+            AttributeValue {
+                m: Some(fields),
+                ..AttributeValue::default()
+            },
+        );
+        assert_eq!(
+            Shape::from_attrs(attrs),
+            Ok(Shape::Rectangle {
+                width: 2,
+                height: 0
+            })
+        );
+    }
+
+    #[test]
+    fn enum_unknown_variant() {
+        let mut attrs = Attributes::new();
+        attrs.insert(
+            "Triangle".into(),
+            AttributeValue {
+                m: Some(Attributes::new()),
+                ..AttributeValue::default()
+            },
+        );
+        assert!(Shape::from_attrs(attrs).is_err());
+    }
+
+    #[test]
+    fn transparent() {
+        let id = UserId("test".into());
+        assert_eq!(id.clone().into_attr(), "test".to_string().into_attr());
+        assert_eq!(UserId::from_attr(id.clone().into_attr()), Ok(id));
+
+        let email = Email {
+            address: "test@example.com".into(),
+        };
+        assert_eq!(
+            email.clone().into_attr(),
+            "test@example.com".to_string().into_attr()
+        );
+
+        let value = Account {
+            id: UserId("test".into()),
+            email,
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["id"], "test".to_string().into_attr());
+        assert_eq!(Account::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn transparent_generic() {
+        let wrapped = Wrapper(3_u32);
+        assert_eq!(wrapped.clone().into_attr(), 3_u32.into_attr());
+        assert_eq!(Wrapper::from_attr(wrapped.clone().into_attr()), Ok(wrapped));
+
+        let wrapped = Wrapper(UserId("test".into()));
+        assert_eq!(wrapped.clone().into_attr(), "test".to_string().into_attr());
+        assert_eq!(Wrapper::from_attr(wrapped.clone().into_attr()), Ok(wrapped));
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        let value = Strict {
+            id: "test".into(),
+            home: Address {
+                street: "123 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let mut attrs: Attributes = value.clone().into();
+        assert_eq!(Strict::from_attrs(attrs.clone()), Ok(value));
+
+        attrs.insert("typo".into(), "oops".to_string().into_attr());
+        assert_eq!(
+            Strict::from_attrs(attrs),
+            Err(dynomite::AttributeError::UnknownField {
+                name: "typo".into()
+            })
+        );
+    }
+
+    #[test]
+    fn query_condition() {
+        let key = OrderKey {
+            user: "test".into(),
+            id: 1,
+        };
+        let (expression, names, values) = key.query_condition();
+        assert_eq!(expression, "#pk = :pk AND #sk = :sk");
+        assert_eq!(names["#pk"], "user");
+        assert_eq!(names["#sk"], "id");
+        assert_eq!(values[":pk"], "test".to_string().into_attr());
+        assert_eq!(values[":sk"], 1_u64.into_attr());
+    }
+
+    #[test]
+    fn version() {
+        let mut value = Document {
+            id: "test".into(),
+            version: 1,
+        };
+        let (expression, names, values) = value.condition_expression_for_save();
+        assert_eq!(expression, "#version = :expected");
+        assert_eq!(names["#version"], "rev");
+        assert_eq!(values[":expected"], 1_u64.into_attr());
+
+        value.increment_version();
+        assert_eq!(value.version, 2);
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["rev"], 2_u64.into_attr());
+
+        value.version = u64::MAX;
+        value.increment_version();
+        assert_eq!(value.version, 0);
+    }
+
+    #[test]
+    fn timestamps() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+        fn fixed() -> SystemTime {
+            UNIX_EPOCH + Duration::from_secs(1_000)
+        }
+        let value = Post {
+            id: "test".into(),
+            created: None,
+            updated: UNIX_EPOCH,
+        };
+        let attrs: Attributes = dynomite::timestamp::with_clock(fixed, || value.into());
+        assert_eq!(attrs["created"], fixed().into_attr());
+        assert_eq!(attrs["updated"], fixed().into_attr());
+
+        let created = UNIX_EPOCH + Duration::from_secs(1);
+        let value = Post {
+            id: "test".into(),
+            created: Some(created),
+            updated: created,
+        };
+        let attrs: Attributes = dynomite::timestamp::with_clock(fixed, || value.into());
+        assert_eq!(attrs["created"], created.into_attr());
+        assert_eq!(attrs["updated"], fixed().into_attr());
+
+        // reads are unaffected
+        assert_eq!(
+            Post::from_attrs(attrs),
+            Ok(Post {
+                id: "test".into(),
+                created: Some(created),
+                updated: fixed(),
+            })
+        );
+    }
+
+    #[test]
+    fn enum_partition_key() {
+        let value = Ticket {
+            status: Status::Closed,
+            id: 1,
+        };
+        let key = TicketKey {
+            status: Status::Closed,
+            id: 1,
+        };
+        let key_attrs: Attributes = key.clone().into();
+        assert_eq!(value.key(), key_attrs);
+        assert_eq!(key_attrs["status"], Status::Closed.into_attr());
+        assert_eq!(TicketKey::from_attrs(value.key()), Ok(key));
+
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(Ticket::from_attrs(attrs), Ok(value));
+        assert_ne!(
+            Ticket {
+                status: Status::Open,
+                id: 1
+            }
+            .key(),
+            key_attrs
+        );
+    }
+
+    #[test]
+    fn map_and_flatten() {
+        let value = Warehouse {
+            id: "test".into(),
+            address: Address {
+                street: "123 Main St".into(),
+                city: "Springfield".into(),
+            },
+            loading: Address {
+                street: "125 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let attrs: Attributes = value.clone().into();
+        let address = attrs["address"].m.as_ref().expect("expected map");
+        assert_eq!(address["street"], "123 Main St".to_string().into_attr());
+        assert_eq!(
+            attrs["loading_street"],
+            "125 Main St".to_string().into_attr()
+        );
+        assert!(!attrs.contains_key("loading"));
+        assert_eq!(Warehouse::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn ttl() {
+        use std::time::{Duration, UNIX_EPOCH};
+        let value = Session {
+            id: "test".into(),
+            expires: UNIX_EPOCH + Duration::from_secs(1_000),
+            idle: None,
+        };
+        let mut attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["expires"].n, Some("1000".into()));
+        assert_eq!(Session::from_attrs(attrs.clone()), Ok(value.clone()));
+
+        attrs.remove("idle");
+        attrs.insert(
+            "expires".into(),
+            AttributeValue {
+                n: Some("1000.5".into()),
+                ..AttributeValue::default()
+            },
+        );
+        assert_eq!(
+            Session::from_attrs(attrs),
+            Ok(Session {
+                expires: UNIX_EPOCH + Duration::from_millis(1_000_500),
+                ..value
+            })
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn compress() {
+        let value = Blob {
+            id: "test".into(),
+            body: "{\"hello\":\"world\"}".repeat(100),
+        };
+        let mut attrs: Attributes = value.clone().into();
+        assert!(attrs["body"].b.is_some());
+        assert_eq!(Blob::from_attrs(attrs.clone()), Ok(value.clone()));
+
+        // legacy uncompressed values remain readable
+        attrs.insert("body".into(), value.body.clone().into_attr());
+        assert_eq!(Blob::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn derive_default() {
+        let value = Draft {
+            id: "test".into(),
+            ..Draft::default()
+        };
+        assert_eq!(
+            value,
+            Draft {
+                id: "test".into(),
+                words: 0,
+                tags: None,
+            }
+        );
+    }
+
+    #[test]
+    fn attribute_to_field() {
+        assert_eq!(
+            Profile::attribute_to_field("displayName"),
+            Some("display_name")
+        );
+        assert_eq!(Profile::attribute_to_field("display_name"), None);
+        assert_eq!(
+            Customer::attribute_to_field("billing_street"),
+            Some("billing")
+        );
+        assert_eq!(Customer::attribute_to_field("unknown"), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json() {
+        let value = Preferences {
+            id: "test".into(),
+            settings: Settings {
+                theme: "dark".into(),
+                limits: vec![1, 2],
+            },
+        };
+        let mut attrs: Attributes = value.clone().into();
+        assert_eq!(
+            attrs["prefs"].s,
+            Some(r#"{"theme":"dark","limits":[1,2]}"#.into())
+        );
+        assert_eq!(Preferences::from_attrs(attrs.clone()), Ok(value));
+
+        attrs.insert("prefs".into(), "{".to_string().into_attr());
+        assert!(matches!(
+            Preferences::from_attrs(attrs.clone()),
+            Err(dynomite::AttributeError::InvalidValue { .. })
+        ));
+
+        attrs.remove("prefs");
+        assert_eq!(
+            Preferences::from_attrs(attrs),
+            Ok(Preferences {
+                id: "test".into(),
+                settings: Settings::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn approximate_size() {
+        let value = Recipe {
+            id: "abc".into(),
+            servings: 4,
+        };
+        // "RecipeId" + "abc" + "servings" + 4
+        assert_eq!(value.approximate_size(), 8 + 3 + 8 + 2);
+        let value = Consignment {
+            id: "parcel-1".into(),
+            weight: 0.125,
+            fragile: true,
+        };
+        // "id" + "parcel-1" + "weight" + 0.125 + "fragile" + true
+        assert_eq!(value.approximate_size(), 2 + 8 + 6 + 3 + 7 + 1);
+        assert_eq!(value.id, "parcel-1");
+    }
+
+    #[test]
+    fn newtype_partition_key() {
+        let value = Account {
+            id: UserId("test".into()),
+            email: Email {
+                address: "test@example.com".into(),
+            },
+        };
+        let key = AccountKey {
+            id: UserId("test".into()),
+        };
+        let key_attrs: Attributes = key.clone().into();
+        assert_eq!(value.key(), key_attrs);
+        assert_eq!(key_attrs["id"], "test".to_string().into_attr());
+        assert_eq!(AccountKey::from_attrs(value.key()), Ok(key));
+
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(Account::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn ordered() {
+        let value = Profile {
+            id: "test".into(),
+            display_name: "name".into(),
+            age: 1,
+        };
+        let attrs = value.into_ordered_attributes();
+        assert_eq!(
+            attrs.keys().collect::<Vec<_>>(),
+            vec!["age", "displayName", "id"]
+        );
+    }
+
+    #[test]
+    fn key_struct() {
+        let value = Recipe {
+            id: "test".into(),
+            servings: 1,
+        };
+        assert_eq!(value.key_struct(), RecipeKey { id: "test".into() });
+        let key_attrs: Attributes = value.key_struct().into();
+        assert_eq!(key_attrs, value.key());
+
+        let value = Order {
+            user: "test".into(),
+            id: 1,
+        };
+        assert_eq!(
+            OrderKey::from(&value),
+            OrderKey {
+                user: "test".into(),
+                id: 1
+            }
+        );
+    }
+
+    #[test]
+    fn from_ref() {
+        let value = Customer {
+            id: "test".into(),
+            billing: Address {
+                street: "123 Main St".into(),
+                city: "Springfield".into(),
+            },
+            shipping: Address {
+                street: "125 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let attrs = Attributes::from(&value);
+        assert_eq!(attrs, Attributes::from(value.clone()));
+        assert_eq!(Customer::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn recursive() {
+        let value = Node {
+            id: "child".into(),
+            parent: Some(Box::new(Node {
+                id: "parent".into(),
+                parent: Some(Box::new(Node {
+                    id: "root".into(),
+                    parent: None,
+                })),
+            })),
+        };
+        let attrs: Attributes = value.clone().into();
+        assert!(attrs["parent"].m.is_some());
+        assert_eq!(Node::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn phantom_data_is_skipped() {
+        let value = Record::<Published> {
+            id: "test".into(),
+            marker: std::marker::PhantomData,
+        };
+        let attrs: Attributes = value.clone().into();
+        assert!(!attrs.contains_key("marker"));
+        assert_eq!(Record::<Published>::attribute_to_field("marker"), None);
+        assert_eq!(Record::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn track_presence() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        attrs.insert("count".into(), 0_u32.into_attr());
+        let value = Migrated::from_attrs(attrs).expect("expected item");
+        assert_eq!(value.count, 0);
+        assert!(value.present.contains("count"));
+        assert!(!value.present.contains("note"));
+
+        let attrs: Attributes = value.into();
+        assert!(!attrs.contains_key("present"));
+
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        let value = Migrated::from_attrs(attrs).expect("expected item");
+        assert_eq!(value.count, 0);
+        assert!(value.present.is_empty());
+    }
+
+    #[test]
+    fn case_insensitive_variants() {
+        for legacy in &["Active", "ACTIVE", "active"] {
+            let value = AttributeValue {
+                s: Some(legacy.to_string()),
+                ..AttributeValue::default()
+            };
+            assert_eq!(Standing::from_attr(value), Ok(Standing::Active));
+        }
+        assert_eq!(Standing::Suspended.into_attr().s, Some("Suspended".into()));
+        assert_eq!(
+            Standing::from_attr(Standing::Suspended.into_attr()),
+            Ok(Standing::Suspended)
+        );
+        assert_eq!(
+            Standing::from_attr(AttributeValue {
+                s: Some("banned".into()),
+                ..AttributeValue::default()
+            }),
+            Err(dynomite::AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn other_variants() {
+        let value = AttributeValue {
+            s: Some("North".into()),
+            ..AttributeValue::default()
+        };
+        let region = Region::from_attr(value.clone()).expect("expected region");
+        assert_eq!(region, Region::Unknown("North".into()));
+        assert_eq!(region.into_attr(), value);
+        assert_eq!(
+            Region::from_attr(Region::East.into_attr()),
+            Ok(Region::East)
+        );
+
+        assert_eq!(Tier::from_attr(value), Ok(Tier::Unrecognized));
+        assert_eq!(Tier::from_attr(Tier::Free.into_attr()), Ok(Tier::Free));
+    }
+
+    #[test]
+    fn item_inputs() {
+        let value = Author {
+            name: "test".into(),
+        };
+        let get = value.get_input("authors");
+        assert_eq!(get.table_name, "authors");
+        assert_eq!(get.key, value.key());
+
+        let put = value.clone().put_input("authors");
+        assert_eq!(put.table_name, "authors");
+        assert_eq!(put.item, Attributes::from(value));
+    }
+
+    #[test]
+    fn update_struct() {
+        let update = ListingUpdate {
+            title: Some("Bike".into()),
+            note: Some(None),
+            ..ListingUpdate::default()
+        };
+        let (expression, values) = update.update_expression();
+        assert_eq!(expression, "SET listingTitle = :title REMOVE note");
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[":title"], "Bike".to_string().into_attr());
+
+        let update = ListingUpdate {
+            price: Some(10),
+            note: Some(Some("used".into())),
+            ..ListingUpdate::default()
+        };
+        let (expression, values) = update.update_expression();
+        assert_eq!(expression, "SET price = :price, note = :note");
+        assert_eq!(values[":note"], "used".to_string().into_attr());
+
+        assert_eq!(ListingUpdate::default().update_expression().0, "");
+    }
+
+    #[test]
+    fn composite_sort_key() {
+        let value = OrderLine {
+            customer: "alice".into(),
+            order: 123,
+            kind: "ORDER".into(),
+            quantity: 2,
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["sk"].s, Some("ORDER#123".into()));
+        assert!(!attrs.contains_key("order"));
+        assert!(!attrs.contains_key("kind"));
+        assert_eq!(value.key()["sk"], attrs["sk"]);
+        assert_eq!(OrderLine::from_attrs(attrs), Ok(value.clone()));
+
+        let key = value.key_struct();
+        assert_eq!(Attributes::from(key.clone()), value.key());
+        let (expression, names, values) = key.query_condition();
+        assert_eq!(expression, "#pk = :pk AND #sk = :sk");
+        assert_eq!(names["#sk"], "sk");
+        assert_eq!(values[":sk"].s, Some("ORDER#123".into()));
+        assert_eq!(OrderLine::attribute_to_field("sk"), Some("kind"));
+    }
+
+    #[test]
+    fn try_from_attributes() {
+        use std::convert::{TryFrom, TryInto};
+        let value = Author {
+            name: "test".into(),
+        };
+        let attrs = Attributes::from(&value);
+        assert_eq!(Author::try_from(attrs.clone()), Ok(value.clone()));
+        let converted: Result<Author, _> = attrs.try_into();
+        assert_eq!(converted, Ok(value));
+        assert!(Author::try_from(Attributes::new()).is_err());
+    }
 
     #[test]
-    fn derived_key() {
-        let value = Recipe {
+    fn key_as() {
+        let value = Product {
+            sku: Sku("abc".into()),
+            name: "widget".into(),
+        };
+        let key: ProductKey = value.key_struct();
+        assert_eq!(key.sku, "abc".to_string());
+        assert_eq!(Attributes::from(key), value.key());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Region::East.to_string(), "East");
+        assert_eq!(Region::Unknown("North".into()).to_string(), "North");
+    }
+
+    #[test]
+    fn key_struct_drops_foreign_attributes() {
+        let value = Contact { id: "test".into() };
+        assert_eq!(Attributes::from(value.key_struct()), value.key());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("ACTIVE".parse::<Standing>(), Ok(Standing::Active));
+        assert_eq!(
+            "banned".parse::<Standing>(),
+            Err(dynomite::AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn attribute_names() {
+        assert_eq!(
+            Listing::ATTRIBUTE_NAMES,
+            &["id", "listingTitle", "price", "note"]
+        );
+        assert_eq!(OrderLine::ATTRIBUTE_NAMES, &["customer", "sk", "quantity"]);
+        assert_eq!(Record::<Published>::ATTRIBUTE_NAMES, &["id"]);
+    }
+
+    #[test]
+    fn default_on_error_falls_back_on_invalid_values() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        assert_eq!(
+            Lenient::from_attrs(attrs.clone()),
+            Ok(Lenient {
+                id: "test".into(),
+                strict: 0,
+                lenient: 0,
+            })
+        );
+
+        attrs.insert("lenient".into(), "corrupt".to_string().into_attr());
+        assert_eq!(
+            Lenient::from_attrs(attrs.clone()).map(|value| value.lenient),
+            Ok(0)
+        );
+
+        attrs.insert("strict".into(), "corrupt".to_string().into_attr());
+        assert_eq!(
+            Lenient::from_attrs(attrs),
+            Err(dynomite::AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    fn schema() {
+        use dynomite::schema::{AttributeDefinition, KeySchemaElement, KeyType, ScalarType};
+        fn key(
+            name: &str,
+            key_type: KeyType,
+        ) -> KeySchemaElement {
+            KeySchemaElement {
+                attribute_name: name.into(),
+                key_type,
+            }
+        }
+        fn definition(
+            name: &str,
+            attribute_type: ScalarType,
+        ) -> AttributeDefinition {
+            AttributeDefinition {
+                attribute_name: name.into(),
+                attribute_type,
+            }
+        }
+
+        let schema = Digest::schema();
+        assert_eq!(schema.key_schema, vec![key("digest", KeyType::Hash)]);
+        assert_eq!(
+            schema.attribute_definitions,
+            vec![definition("digest", ScalarType::B)]
+        );
+
+        let schema = FixedDigest::schema();
+        assert_eq!(
+            schema.attribute_definitions,
+            vec![definition("digest", ScalarType::B)]
+        );
+
+        let schema = Ticket::schema();
+        assert_eq!(
+            schema.key_schema,
+            vec![key("status", KeyType::Hash), key("id", KeyType::Range)]
+        );
+        assert_eq!(
+            schema.attribute_definitions,
+            vec![
+                definition("status", ScalarType::S),
+                definition("id", ScalarType::N)
+            ]
+        );
+
+        let schema = OrderLine::schema();
+        assert_eq!(
+            schema.key_schema,
+            vec![key("customer", KeyType::Hash), key("sk", KeyType::Range)]
+        );
+        assert_eq!(
+            schema.attribute_definitions,
+            vec![
+                definition("customer", ScalarType::S),
+                definition("sk", ScalarType::S)
+            ]
+        );
+    }
+
+    #[test]
+    fn encrypt_with() {
+        let value = Patient {
             id: "test".into(),
-            servings: 1,
+            ssn: "123-45-6789".into(),
         };
-        assert_eq!(value.key(), RecipeKey { id: "test".into() }.into());
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["id"].s, Some("test".into()));
+        assert_eq!(attrs["ssn"].s, None);
+        assert_ne!(attrs["ssn"].b.as_deref(), Some("123-45-6789".as_bytes()));
+        assert_eq!(Patient::from_attrs(attrs), Ok(value));
+
+        // values which fail to decrypt fail the conversion
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "test".to_string().into_attr());
+        attrs.insert("ssn".into(), "123-45-6789".to_string().into_attr());
+        assert_eq!(
+            Patient::from_attrs(attrs),
+            Err(dynomite::AttributeError::InvalidType)
+        );
     }
 
     #[test]
-    fn to_and_from_book() {
-        let value = Book {
-            title: "rust".into(),
-            ..Default::default()
+    fn encrypt_with_errors_fail_try_into_attributes() {
+        let value = Prescription {
+            id: "test".into(),
+            dose: 5,
+        };
+        assert_eq!(
+            value.try_into_attributes(),
+            Err(dynomite::AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    fn scan_filters() {
+        let (expression, names, values) = Ticket::status_eq(Status::Open);
+        assert_eq!(expression, "#status = :status");
+        assert_eq!(names["#status"], "status");
+        assert_eq!(values[":status"], Status::Open.into_attr());
+
+        let (expression, names, values) = Profile::display_name_eq("Jane".into());
+        assert_eq!(expression, "#display_name = :display_name");
+        assert_eq!(names["#display_name"], "displayName");
+        assert_eq!(values[":display_name"], "Jane".to_string().into_attr());
+
+        let (expression, _, _) = Reply::body_begins_with("Thanks");
+        assert_eq!(expression, "begins_with(#body, :body)");
+
+        let value = Memo {
+            id: "test".into(),
+            title: "groceries".into(),
+        };
+        assert!(value.title_eq("groceries"));
+
+        let (expression, names, values) = Thread::subject_begins_with("Re:");
+        assert_eq!(expression, "begins_with(#subject, :subject)");
+        assert_eq!(names["#subject"], "subject");
+        assert_eq!(values[":subject"], "Re:".to_string().into_attr());
+    }
+
+    #[test]
+    fn query_builder() {
+        let input = OrderQuery::partition("alice".into())
+            .sort_between(1, 10)
+            .limit(5)
+            .index("by_id")
+            .scan_forward(false)
+            .into_input("orders");
+        assert_eq!(input.table_name, "orders");
+        assert_eq!(
+            input.key_condition_expression.as_deref(),
+            Some("#pk = :pk AND #sk BETWEEN :sk_start AND :sk_end")
+        );
+        let names = input.expression_attribute_names.unwrap_or_default();
+        assert_eq!(names["#pk"], "user");
+        assert_eq!(names["#sk"], "id");
+        let values = input.expression_attribute_values.unwrap_or_default();
+        assert_eq!(values[":pk"], "alice".to_string().into_attr());
+        assert_eq!(values[":sk_start"], 1_u64.into_attr());
+        assert_eq!(values[":sk_end"], 10_u64.into_attr());
+        assert_eq!(input.limit, Some(5));
+        assert_eq!(input.index_name.as_deref(), Some("by_id"));
+        assert_eq!(input.scan_index_forward, Some(false));
+
+        let input = OrderQuery::partition("alice".into())
+            .sort_eq(1)
+            .into_input("orders");
+        assert_eq!(
+            input.key_condition_expression.as_deref(),
+            Some("#pk = :pk AND #sk = :sk")
+        );
+        assert_eq!(input.limit, None);
+
+        let input = ThreadQuery::partition("rust".into())
+            .sort_begins_with("Re:")
+            .into_input("threads");
+        assert_eq!(
+            input.key_condition_expression.as_deref(),
+            Some("#pk = :pk AND begins_with(#sk, :sk)")
+        );
+        assert_eq!(
+            input.expression_attribute_values.unwrap_or_default()[":sk"],
+            "Re:".to_string().into_attr()
+        );
+
+        let input = ReplyQuery::partition("rust".into())
+            .sort_begins_with("Thanks")
+            .into_input("replies");
+        assert_eq!(
+            input.key_condition_expression.as_deref(),
+            Some("#pk = :pk AND begins_with(#sk, :sk)")
+        );
+
+        let input = OrderLineQuery::partition("alice".into())
+            .sort_begins_with("ORDER#")
+            .into_input("orders");
+        assert_eq!(
+            input.expression_attribute_names.unwrap_or_default()["#sk"],
+            "sk"
+        );
+
+        let input = DraftQuery::partition("test".into()).into_input("drafts");
+        assert_eq!(input.key_condition_expression.as_deref(), Some("#pk = :pk"));
+        assert_eq!(
+            input.expression_attribute_names.unwrap_or_default().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn from_attrs_ref() {
+        use std::sync::Arc;
+        let value = Customer {
+            id: "test".into(),
+            billing: Address {
+                street: "1 Main St".into(),
+                city: "Springfield".into(),
+            },
+            shipping: Address {
+                street: "2 Main St".into(),
+                city: "Shelbyville".into(),
+            },
+        };
+        let mut attrs: Attributes = value.clone().into();
+        attrs.insert("unrelated".into(), "ignored".to_string().into_attr());
+        let attrs = Arc::new(attrs);
+        assert_eq!(Customer::from_attrs_ref(&attrs), Ok(value));
+        assert_eq!(
+            Draft::from_attrs_ref(&attrs),
+            Ok(Draft {
+                id: "test".into(),
+                words: 0,
+                tags: None,
+            })
+        );
+        assert!(Strict::from_attrs_ref(&attrs).is_err());
+        assert_eq!(attrs.len(), 6);
+    }
+
+    #[test]
+    fn key_rename() {
+        let value = Migrating {
+            id: "test".into(),
+            version: 1,
         };
         let attrs: Attributes = value.clone().into();
-        assert_eq!(value, Book::from_attrs(attrs).unwrap())
+        assert!(attrs.contains_key("newId"));
+        assert!(attrs.contains_key("version"));
+
+        let key = value.key();
+        assert_eq!(key["oldId"], "test".to_string().into_attr());
+        assert_eq!(key["legacyVersion"], 1.into_attr());
+        assert_eq!(key.len(), 2);
+
+        let key: Attributes = value.key_struct().into();
+        assert!(key.contains_key("oldId"));
+        assert!(key.contains_key("legacyVersion"));
+        assert_eq!(value.key_struct().key(), value.key());
     }
 
     #[test]
-    fn derive_attr() {
-        #[derive(Attribute, Debug, PartialEq)]
-        enum Foo {
-            Bar,
+    fn crate_path() {
+        let value = Facade {
+            id: "test".into(),
+            kind: FacadeKind::External,
         };
-        assert_eq!(Foo::Bar, Foo::from_attr(Foo::Bar.into_attr()).unwrap());
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["kind"], "External".to_string().into_attr());
+        assert_eq!(Facade::from_attrs(attrs), Ok(value.clone()));
+        assert_eq!(value.key_struct().key(), value.key());
     }
 
     #[test]
-    fn field_rename() {
-        let value = Recipe {
-            id: "aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee".into(),
+    fn flatten_key() {
+        let value = Entity {
+            key: PrimaryKey {
+                pk: "USER#1".into(),
+                sk: "PROFILE".into(),
+            },
+            name: "test".into(),
+        };
+        let key = value.key();
+        assert_eq!(key, value.key.key());
+        assert_eq!(key.len(), 2);
+        assert_eq!(
+            value.key_struct(),
+            PrimaryKeyKey {
+                pk: "USER#1".into(),
+                sk: "PROFILE".into(),
+            }
+        );
+        assert_eq!(Entity::schema(), PrimaryKey::schema());
+
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(Entity::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn flatten_newtype() {
+        let address = Address {
+            street: "1 Main St".into(),
+            city: "Springfield".into(),
+        };
+        let value = Shipping(address.clone());
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs, Attributes::from(address.clone()));
+        assert_eq!(Shipping::from_attrs(attrs), Ok(value.clone()));
+        assert_eq!(value.clone().into_attr(), address.into_attr());
+        assert_eq!(Shipping::from_attr(value.clone().into_attr()), Ok(value));
+    }
+
+    #[test]
+    fn prefixed_variants() {
+        assert_eq!(Stage::Active.into_attr().s, Some("STATUS#active".into()));
+        assert_eq!(Stage::Closed.to_string(), "STATUS#closed");
+        assert_eq!(
+            Stage::from_attr(Stage::Closed.into_attr()),
+            Ok(Stage::Closed)
+        );
+        for unprefixed in &["active", "Active", "STATUS#Active"] {
+            assert_eq!(
+                Stage::from_attr(AttributeValue {
+                    s: Some(unprefixed.to_string()),
+                    ..AttributeValue::default()
+                }),
+                Err(dynomite::AttributeError::InvalidFormat)
+            );
+        }
+    }
+
+    #[test]
+    fn lenient_prefixed_variants() {
+        assert_eq!(Priority::High.into_attr().s, Some("PRIORITY#High".into()));
+        for legacy in &["PRIORITY#Low", "Low"] {
+            assert_eq!(
+                Priority::from_attr(AttributeValue {
+                    s: Some(legacy.to_string()),
+                    ..AttributeValue::default()
+                }),
+                Ok(Priority::Low)
+            );
+        }
+    }
+
+    #[test]
+    fn internally_tagged_enum_variants() {
+        let value = Payment::Card {
+            last4: "1234".into(),
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs["kind"].s, Some("Card".into()));
+        assert_eq!(attrs["last4"].s, Some("1234".into()));
+        assert_eq!(Payment::from_attrs(attrs), Ok(value));
+        assert_eq!(
+            Payment::from_attr(Payment::Cash {}.into_attr()),
+            Ok(Payment::Cash {})
+        );
+        assert_eq!(
+            Payment::from_attrs(Attributes::new()),
+            Err(dynomite::AttributeError::MissingField {
+                name: "kind".into()
+            })
+        );
+    }
+
+    #[test]
+    fn adjacently_tagged_enum_variants() {
+        let value = Notification::Sms {
+            number: "555".into(),
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(attrs.len(), 2);
+        assert_eq!(attrs["kind"].s, Some("Sms".into()));
+        let fields = attrs["data"].m.as_ref().expect("expected map");
+        assert_eq!(fields["number"].s, Some("555".into()));
+        assert_eq!(Notification::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn shared_key_structs() {
+        let invoice = Invoice {
+            tenant: "acme".into(),
+            id: "1".into(),
+            total: 10,
+        };
+        let receipt = Receipt {
+            tenant: "acme".into(),
+            id: "1".into(),
+        };
+        let key: TenantKey = invoice.key_struct();
+        assert_eq!(key, receipt.key_struct());
+        let attrs: Attributes = key.into();
+        assert_eq!(attrs, invoice.key());
+    }
+
+    #[test]
+    fn aliased_fields() {
+        let mut attrs = Attributes::new();
+        attrs.insert("username".into(), "jane".to_string().into_attr());
+        attrs.insert(
+            "emailAddress".into(),
+            "jane@example.com".to_string().into_attr(),
+        );
+        let member = Member::from_attrs(attrs).expect("expected member");
+        assert_eq!(
+            member,
+            Member {
+                user_name: "jane".into(),
+                email: "jane@example.com".into(),
+            }
+        );
+        let attrs: Attributes = member.into();
+        assert!(attrs.contains_key("user_name"));
+        assert!(!attrs.contains_key("userName"));
+
+        let mut attrs = Attributes::new();
+        attrs.insert("user_name".into(), "new".to_string().into_attr());
+        attrs.insert("userName".into(), "old".to_string().into_attr());
+        assert_eq!(
+            Member::from_attrs(attrs).map(|member| member.user_name),
+            Ok("new".to_string())
+        );
+        assert_eq!(
+            Member::from_attrs(Attributes::new()),
+            Err(dynomite::AttributeError::MissingField {
+                name: "user_name".into()
+            })
+        );
+    }
+
+    #[test]
+    fn into_key_moves_key_fields() {
+        let order = Order {
+            user: "user".into(),
+            id: 1,
+        };
+        assert_eq!(order.key(), order.clone().into_key());
+
+        let line = OrderLine {
+            customer: "customer".into(),
+            order: 2,
+            kind: "book".into(),
+            quantity: 1,
+        };
+        assert_eq!(line.key(), line.clone().into_key());
+
+        let entity = Entity {
+            key: PrimaryKey {
+                pk: "pk".into(),
+                sk: "sk".into(),
+            },
+            name: "name".into(),
+        };
+        assert_eq!(entity.key(), entity.clone().into_key());
+    }
+
+    #[test]
+    fn cfg_fields() {
+        let audited = Audited {
+            id: "1".into(),
+            #[cfg(feature = "gzip")]
+            audit_log: vec!["created".into()],
+            #[cfg(not(feature = "gzip"))]
+            revision: 1,
+        };
+        let attrs: Attributes = audited.clone().into();
+        assert_eq!(attrs.contains_key("audit_log"), cfg!(feature = "gzip"));
+        assert_eq!(attrs.contains_key("revision"), !cfg!(feature = "gzip"));
+        assert_eq!(Audited::ATTRIBUTE_NAMES.len(), 2);
+        assert_eq!(Audited::from_attrs(attrs), Ok(audited));
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn epoch_fields() {
+        use chrono::offset::TimeZone;
+        let time = chrono::Utc
+            .ymd(2020, 9, 13)
+            .and_hms_micro(12, 26, 40, 250_001);
+        let value = Measurement {
+            taken: time,
+            recorded: time,
+            verified: None,
+        };
+        let attrs: Attributes = value.clone().into();
+        assert_eq!(
+            attrs["taken"].s,
+            Some("2020-09-13T12:26:40.250001+00:00".into())
+        );
+        assert_eq!(attrs["recorded"].n, Some("1600000000.250001".into()));
+        assert_eq!(attrs["verified"].null, Some(true));
+        assert_eq!(Measurement::from_attrs(attrs), Ok(value));
+    }
+
+    #[test]
+    fn fields_in_declaration_order() {
+        let value = Coordinates {
+            latitude: 1,
+            longitude: 2,
+            altitude: Altitude { meters: 3 },
+        };
+        let fields = value.fields();
+        assert_eq!(
+            fields
+                .iter()
+                .map(|(name, _)| name.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["lat", "longitude", "alt_meters"]
+        );
+        assert_eq!(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.into_owned(), value))
+                .collect::<Attributes>(),
+            Attributes::from(value)
+        );
+    }
+
+    #[test]
+    fn key_strings() {
+        let visit = Visit {
+            person: "people/123".into(),
+            day: 20210501,
+        };
+        assert_eq!(visit.key_string(), "people\\/123/20210501");
+        let key = visit.key_struct();
+        assert_eq!(key.key_string(), visit.key_string());
+        assert_eq!(VisitKey::parse_key_string(&visit.key_string()), Ok(key));
+        assert_eq!(
+            VisitKey::parse_key_string("people/tomorrow"),
+            Err(dynomite::AttributeError::InvalidFormat)
+        );
+
+        let line = OrderLine {
+            customer: "customer".into(),
+            order: 2,
+            kind: "book".into(),
+            quantity: 1,
+        };
+        assert_eq!(line.key_string(), "customer#book#2");
+        assert_eq!(
+            OrderLineKey::parse_key_string("customer#book#2"),
+            Ok(line.key_struct())
+        );
+    }
+
+    #[test]
+    fn read_only_fields() {
+        let mut attrs = Attributes::new();
+        attrs.insert("id".into(), "1".to_string().into_attr());
+        attrs.insert("legacyName".into(), "old".to_string().into_attr());
+        let archived = Archived::from_attrs(attrs).expect("expected item");
+        assert_eq!(archived.legacy_name, "old");
+
+        let attrs: Attributes = archived.into();
+        assert!(!attrs.contains_key("legacyName"));
+        assert_eq!(
+            Archived::from_attrs(attrs),
+            Ok(Archived {
+                id: "1".into(),
+                legacy_name: String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn flattened_maps_with_prefix() {
+        let mut titles = std::collections::HashMap::new();
+        titles.insert("en".to_string(), "Spirited Away".to_string());
+        titles.insert("ja".to_string(), "Sen to Chihiro".to_string());
+        let movie = Movie {
+            id: "1".into(),
+            titles,
+            year: 2001,
+        };
+        let attrs: Attributes = movie.clone().into();
+        assert_eq!(attrs.len(), 4);
+        assert_eq!(
+            attrs.get("title_en").and_then(|value| value.s.clone()),
+            Some("Spirited Away".to_string())
+        );
+        assert_eq!(Movie::from_attrs(attrs), Ok(movie));
+    }
+
+    #[test]
+    fn rename_all_fields() {
+        let subscriber = Subscriber {
+            email_address: "jane@example.com".into(),
+            plan_name: "pro".into(),
+            display_name: "Jane".into(),
+        };
+        let attrs: Attributes = subscriber.clone().into();
+        assert!(attrs.contains_key("emailAddress"));
+        assert!(attrs.contains_key("plan"));
+        assert!(attrs.contains_key("displayName"));
+        assert_eq!(Subscriber::from_attrs(attrs), Ok(subscriber.clone()));
+
+        let key: Attributes = subscriber.key_struct().into();
+        assert_eq!(key, subscriber.key());
+        assert!(key.contains_key("emailAddress"));
+        assert_eq!(
+            SubscriberKey::from_attrs(key),
+            Ok(SubscriberKey {
+                email_address: "jane@example.com".into(),
+                plan_name: "pro".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn renamed_fields_read_aliases() {
+        for stored in &["newName", "oldName", "legacyName"] {
+            let mut attrs = Attributes::new();
+            attrs.insert(stored.to_string(), "jane".to_string().into_attr());
+            let handle = Handle::from_attrs(attrs).expect("expected handle");
+            assert_eq!(handle.name, "jane");
+            let attrs: Attributes = handle.into();
+            assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["newName"]);
+        }
+    }
+
+    #[test]
+    fn literal_defaults() {
+        let expected = UserDefaults {
+            user: "jane".into(),
+            page_size: 42,
+            notifications: true,
+            zoom: 1.5,
+            theme: "unknown".into(),
+        };
+        let mut attrs = Attributes::new();
+        attrs.insert("user".into(), "jane".to_string().into_attr());
+        assert_eq!(
+            UserDefaults::from_attrs(attrs.clone()),
+            Ok(expected.clone())
+        );
+
+        attrs.insert("theme".into(), 1.into_attr());
+        assert_eq!(UserDefaults::from_attrs(attrs), Ok(expected.clone()));
+
+        assert_eq!(
+            UserDefaults::default(),
+            UserDefaults {
+                user: String::new(),
+                ..expected
+            }
+        );
+    }
+
+    #[test]
+    fn merged_items() {
+        let mut profile = CachedProfile {
+            id: "1".into(),
+            name: "Jane".into(),
+            age: 30,
+            nickname: Some("jj".into()),
+        };
+        profile.merge(CachedProfile {
+            id: "2".into(),
+            name: String::new(),
+            age: 31,
+            nickname: None,
+        });
+        assert_eq!(
+            profile,
+            CachedProfile {
+                id: "1".into(),
+                name: "Jane".into(),
+                age: 31,
+                nickname: Some("jj".into()),
+            }
+        );
+        profile.merge(CachedProfile {
+            id: "1".into(),
+            name: "Janet".into(),
+            age: 0,
+            nickname: Some("jay".into()),
+        });
+        assert_eq!(profile.name, "Janet");
+        assert_eq!(profile.age, 31);
+        assert_eq!(profile.nickname, Some("jay".into()));
+    }
+
+    #[test]
+    fn lenient_numbers() {
+        let expected = Reading {
+            id: "1".into(),
+            value: 1.5,
+            count: Some(3),
+        };
+        let canonical: Attributes = expected.clone().into();
+        assert_eq!(canonical["value"].n, Some("1.5".into()));
+        assert_eq!(Reading::from_attrs(canonical), Ok(expected.clone()));
+
+        let mut legacy = Attributes::new();
+        legacy.insert("id".into(), "1".to_string().into_attr());
+        legacy.insert("value".into(), "1.5".to_string().into_attr());
+        legacy.insert("count".into(), "3".to_string().into_attr());
+        assert_eq!(Reading::from_attrs(legacy.clone()), Ok(expected));
+
+        legacy.insert("value".into(), "many".to_string().into_attr());
+        assert_eq!(
+            Reading::from_attrs(legacy),
+            Err(dynomite::AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn table_name_const() {
+        assert_eq!(Subscription::TABLE_NAME, "subscriptions");
+        let subscription = Subscription { id: "1".into() };
+        assert_eq!(
+            subscription.get_input(Subscription::TABLE_NAME).table_name,
+            "subscriptions"
+        );
+    }
+
+    #[test]
+    fn existence_conditions() {
+        let (expression, names) = Recipe::not_exists_condition();
+        assert_eq!(expression, "attribute_not_exists(#pk)");
+        assert_eq!(names["#pk"], "RecipeId");
+        let (expression, names) = Recipe::exists_condition();
+        assert_eq!(expression, "attribute_exists(#pk)");
+        assert_eq!(names.len(), 1);
+        assert_eq!(names["#pk"], "RecipeId");
+    }
+
+    #[test]
+    fn enum_short_codes() {
+        assert_eq!(Availability::InStock.into_attr().s, Some("A".into()));
+        assert_eq!(
+            Availability::from_attr(Availability::Discontinued.into_attr()),
+            Ok(Availability::Discontinued)
+        );
+        assert_eq!(
+            Availability::from_attr("Backordered".to_string().into_attr()),
+            Err(dynomite::AttributeError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn roundtrip_assert() {
+        dynomite::roundtrip::assert(Recipe {
+            id: "test".into(),
+            servings: 2,
+        });
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn recipes_roundtrip(id in "[a-zA-Z0-9]+", servings in proptest::prelude::any::<u64>()) {
+            dynomite::roundtrip::check(Recipe { id, servings })?;
+        }
+    }
+
+    #[test]
+    fn skip_serializing_and_deserializing() {
+        let entry = SearchEntry {
+            id: "1".into(),
+            legacy_rank: 3,
+            search_terms: vec!["rust".into()],
+        };
+        let attrs: Attributes = entry.into();
+        assert!(!attrs.contains_key("legacy_rank"));
+        assert_eq!(
+            attrs.get("searchTerms").and_then(|value| value.l.clone()),
+            Some(vec!["rust".to_string().into_attr()])
+        );
+        // skipped fields are still required when read
+        assert_eq!(
+            SearchEntry::from_attrs(attrs.clone()),
+            Err(dynomite::AttributeError::MissingField {
+                name: "legacy_rank".into()
+            })
+        );
+        let mut attrs = attrs;
+        attrs.insert("legacy_rank".into(), 5.into_attr());
+        assert_eq!(
+            SearchEntry::from_attrs(attrs),
+            Ok(SearchEntry {
+                id: "1".into(),
+                legacy_rank: 5,
+                search_terms: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn wide_integer_keys() {
+        use dynomite::schema::ScalarType;
+        let snowflake = Snowflake {
+            id: u128::MAX / 10,
+            sequence: -1,
+        };
+        assert_eq!(
+            Snowflake::from_attrs(snowflake.clone().into()),
+            Ok(snowflake.clone())
+        );
+        assert_eq!(snowflake.key().len(), 2);
+        assert!(Snowflake::schema()
+            .attribute_definitions
+            .iter()
+            .all(|definition| definition.attribute_type == ScalarType::N));
+    }
+
+    #[test]
+    fn from_attrs_mut_leaves_other_attributes() {
+        let mut attrs: Attributes = Recipe {
+            id: "1".into(),
             servings: 2,
+        }
+        .into();
+        attrs.insert("note".into(), "spicy".to_string().into_attr());
+        assert_eq!(
+            Recipe::from_attrs_mut(&mut attrs),
+            Ok(Recipe {
+                id: "1".into(),
+                servings: 2,
+            })
+        );
+        assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["note"]);
+        assert_eq!(
+            std::collections::HashMap::<String, String>::from_attrs_mut(&mut attrs),
+            Ok(vec![("note".to_string(), "spicy".to_string())]
+                .into_iter()
+                .collect())
+        );
+        assert!(attrs.is_empty());
+    }
+
+    #[test]
+    fn try_into_attributes_rejects_non_finite_floats() {
+        let station = Station {
+            id: "1".into(),
+            temperature: 21.5,
+            location: GeoPoint {
+                latitude: 1.0,
+                longitude: 2.0,
+            },
+        };
+        assert_eq!(
+            station.clone().try_into_attributes(),
+            Ok(Attributes::from(station.clone()))
+        );
+        let invalid = Station {
+            temperature: f64::NAN,
+            ..station.clone()
+        };
+        assert!(invalid.try_into_attributes().is_err());
+        let invalid = Station {
+            location: GeoPoint {
+                latitude: f64::INFINITY,
+                longitude: 2.0,
+            },
+            ..station
+        };
+        assert!(invalid.try_into_attributes().is_err());
+    }
+
+    #[test]
+    fn try_into_attr_checks_wrapped_floats() {
+        let point = GeoPoint {
+            latitude: 1.0,
+            longitude: f64::INFINITY,
+        };
+        assert!(Celsius(f64::NAN).try_into_attr().is_err());
+        assert!(Celsius(21.5).try_into_attr().is_ok());
+        assert!(Beacon {
+            reading: Celsius(f64::NAN),
+            position: GeoPoint {
+                latitude: 1.0,
+                longitude: 2.0,
+            },
+        }
+        .try_into_attributes()
+        .is_err());
+        assert!(Beacon {
+            reading: Celsius(21.5),
+            position: point.clone(),
+        }
+        .try_into_attributes()
+        .is_err());
+        assert!(Position(point).try_into_attr().is_err());
+        assert!(Area::Circle { radius: f64::NAN }.try_into_attr().is_err());
+        assert_eq!(
+            Area::Circle { radius: 1.0 }.try_into_attributes(),
+            Ok(Attributes::from(Area::Circle { radius: 1.0 }))
+        );
+    }
+
+    #[test]
+    fn rename_all_serialize_and_deserialize() {
+        let contact = MigratingContact {
+            phone_number: "555".into(),
+            first_name: "Jane".into(),
+        };
+        let attrs: Attributes = contact.clone().into();
+        let mut names = attrs.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["firstName", "phoneNumber"]);
+        assert_eq!(MigratingContact::from_attrs(attrs), Ok(contact.clone()));
+
+        let mut legacy = Attributes::new();
+        legacy.insert("phone_number".into(), "555".to_string().into_attr());
+        legacy.insert("first_name".into(), "Jane".to_string().into_attr());
+        assert_eq!(MigratingContact::from_attrs(legacy), Ok(contact));
+    }
+
+    #[test]
+    fn enum_as_str() {
+        assert_eq!(Availability::Backordered.as_str(), "B");
+        assert_eq!(Stage::Closed.as_str(), "STATUS#closed");
+        let name: &str = Stage::Active.as_ref();
+        assert_eq!(Some(name.to_string()), Stage::Active.into_attr().s);
+    }
+
+    #[test]
+    fn wrapped_attributes() {
+        let shipment = Shipment {
+            tracking_number: "1Z".into(),
+            parcels: 2,
         };
+        let attrs: Attributes = shipment.clone().into();
+        assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["detail"]);
+        let detail = attrs["detail"].m.as_ref().expect("expected a map");
+        assert_eq!(detail["tracking_number"].s, Some("1Z".to_string()));
+        assert_eq!(Shipment::from_attrs(attrs), Ok(shipment.clone()));
+        assert_eq!(
+            shipment
+                .clone()
+                .try_into_attributes()
+                .map(Shipment::from_attrs),
+            Ok(Ok(shipment.clone()))
+        );
+        assert_eq!(shipment.clone().into_attr().m, Some(shipment.into()));
+    }
+
+    #[test]
+    fn wrapped_attributes_require_map_wrapper() {
+        assert_eq!(
+            Shipment::from_attrs(Attributes::new()),
+            Err(dynomite::AttributeError::MissingField {
+                name: "detail".into()
+            })
+        );
+        let mut attrs = Attributes::new();
+        attrs.insert("detail".into(), "1Z".to_string().into_attr());
+        assert_eq!(
+            Shipment::from_attrs(attrs),
+            Err(dynomite::AttributeError::InvalidType)
+        );
+    }
+
+    #[test]
+    fn null_when_default() {
+        let reading = NullableReading {
+            sensor: "a".into(),
+            unit: "celsius".into(),
+            value: 21,
+        };
+        let attrs: Attributes = reading.clone().into();
+        assert_eq!(attrs["unit"].s, Some("celsius".to_string()));
+        assert_eq!(NullableReading::from_attrs(attrs), Ok(reading));
+
+        let empty = NullableReading {
+            sensor: "a".into(),
+            unit: String::new(),
+            value: 0,
+        };
+        let attrs: Attributes = empty.clone().into();
+        assert_eq!(attrs["unit"].null, Some(true));
+        assert_eq!(attrs["value"].null, Some(true));
+        assert_eq!(
+            NullableReading::from_attrs(attrs.clone()),
+            Ok(empty.clone())
+        );
+
+        let mut absent = attrs;
+        absent.remove("value");
+        assert_eq!(NullableReading::from_attrs(absent.clone()), Ok(empty));
+        absent.remove("unit");
+        assert_eq!(
+            NullableReading::from_attrs(absent),
+            Err(dynomite::AttributeError::MissingField {
+                name: "unit".into()
+            })
+        );
+    }
 
+    #[test]
+    fn key_attribute_names() {
+        assert_eq!(Digest::key_attribute_names(), &["digest"]);
+        assert_eq!(Ticket::key_attribute_names(), &["status", "id"]);
+        assert_eq!(OrderLine::key_attribute_names(), &["customer", "sk"]);
+        assert_eq!(Entity::key_attribute_names(), &["pk", "sk"]);
+        let ticket = Ticket {
+            status: Status::Closed,
+            id: 1,
+        }
+        .key();
+        assert!(Ticket::key_attribute_names()
+            .iter()
+            .all(|name| ticket.contains_key(*name)));
+    }
+    #[test]
+    fn skip_query_struct() {
+        let value = Topic {
+            forum: "rust".into(),
+            title: "test".into(),
+        };
         let attrs: Attributes = value.clone().into();
-        assert!(attrs.contains_key("RecipeId"));
-        assert!(!attrs.contains_key("id"));
+        assert_eq!(value, Topic::from_attrs(attrs).unwrap());
+    }
+    #[test]
+    fn get_by_key() {
+        let orders = vec![
+            Order {
+                user: "alice".into(),
+                id: 1,
+            },
+            Order {
+                user: "alice".into(),
+                id: 2,
+            },
+        ];
+        let table = orders
+            .iter()
+            .cloned()
+            .map(Attributes::from)
+            .collect::<Vec<_>>();
+        // resolves a GetItem the way DynamoDB does, by matching each key attribute
+        let get = |key: Attributes| {
+            table
+                .iter()
+                .find(|item| {
+                    key.iter()
+                        .all(|(name, value)| item.get(name) == Some(value))
+                })
+                .cloned()
+                .map(Order::from_attrs)
+        };
+        for order in orders {
+            assert_eq!(get(order.key()), Some(Ok(order.clone())));
+            assert_eq!(get(order.clone().into_key()), Some(Ok(order)));
+        }
+    }
+    #[test]
+    fn rename_all_serialize_and_deserialize_with_flatten() {
+        let supplier = MigratingSupplier {
+            company_name: "Acme".into(),
+            address: Address {
+                street: "1 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let attrs: Attributes = supplier.clone().into();
+        let mut names = attrs.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["city", "companyName", "street"]);
+        assert_eq!(MigratingSupplier::from_attrs(attrs), Ok(supplier.clone()));
 
-        assert_eq!(value, Recipe::from_attrs(attrs).unwrap());
+        let mut legacy = Attributes::new();
+        legacy.insert("company_name".into(), "Acme".to_string().into_attr());
+        legacy.insert("street".into(), "1 Main St".to_string().into_attr());
+        legacy.insert("city".into(), "Springfield".to_string().into_attr());
+        assert_eq!(MigratingSupplier::from_attrs(legacy), Ok(supplier));
+    }
+    #[test]
+    fn from_attrs_mut_removes_flattened_attributes() {
+        let value = Store {
+            id: "1".into(),
+            address: Address {
+                street: "1 Main St".into(),
+                city: "Springfield".into(),
+            },
+        };
+        let mut attrs: Attributes = value.clone().into();
+        attrs.insert("note".into(), "corner".to_string().into_attr());
+        assert_eq!(Store::from_attrs_mut(&mut attrs), Ok(value.clone()));
+        assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["note"]);
+
+        let mut attrs: Attributes = value.address.clone().into();
+        attrs.insert("note".into(), "corner".to_string().into_attr());
+        assert_eq!(
+            Shipping::from_attrs_mut(&mut attrs),
+            Ok(Shipping(value.address))
+        );
+        assert_eq!(attrs.keys().collect::<Vec<_>>(), vec!["note"]);
+    }
+    #[test]
+    fn encrypt_with_fields_are_not_filtered_or_updated() {
+        let value = Refill {
+            id: "1".into(),
+            dose: 5,
+            repeats: 2,
+        };
+        assert!(value.dose_eq(5));
+        assert!(value.clone().try_into_attributes().is_err());
+        let (expression, _, _) = Refill::repeats_eq(2);
+        assert_eq!(expression, "#repeats = :repeats");
+
+        let update = RefillUpdate { repeats: Some(3) };
+        let (expression, values) = update.update_expression();
+        assert_eq!(expression, "SET repeats = :repeats");
+        assert_eq!(values.keys().collect::<Vec<_>>(), vec![":repeats"]);
     }
 }
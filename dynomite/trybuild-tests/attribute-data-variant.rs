@@ -0,0 +1,9 @@
+use dynomite_derive::Attribute;
+
+#[derive(Attribute)]
+enum Status {
+    Active,
+    Archived(String),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use dynomite_derive::Attribute;
+
+#[derive(Attribute)]
+enum Status {
+    Active,
+    #[dynomite(other)]
+    Unknown(u32),
+}
+
+fn main() {}
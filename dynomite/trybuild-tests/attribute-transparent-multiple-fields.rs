@@ -0,0 +1,7 @@
+use dynomite_derive::Attribute;
+
+#[derive(Attribute)]
+#[dynomite(transparent)]
+struct Foo(String, String);
+
+fn main() {}
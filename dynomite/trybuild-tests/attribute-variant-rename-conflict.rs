@@ -0,0 +1,11 @@
+use dynomite_derive::Attribute;
+
+#[derive(Attribute)]
+enum Status {
+    #[dynomite(rename = "A")]
+    Active,
+    #[dynomite(rename = "A")]
+    Archived,
+}
+
+fn main() {}
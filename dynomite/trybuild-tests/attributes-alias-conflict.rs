@@ -0,0 +1,10 @@
+use dynomite_derive::Attributes;
+
+#[derive(Attributes)]
+struct Foo {
+    user_name: String,
+    #[dynomite(alias = "user_name")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(parition_key)]
+    key: String,
+}
+
+fn main() {}
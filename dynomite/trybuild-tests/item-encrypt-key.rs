@@ -0,0 +1,9 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key, encrypt_with = "xor")]
+    key: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use dynomite_derive::Item;
+use std::time::SystemTime;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    key: String,
+    #[dynomite(sort_key, epoch)]
+    at: SystemTime,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct PrimaryKey {
+    #[dynomite(partition_key)]
+    pk: String,
+}
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    id: String,
+    #[dynomite(flatten, key)]
+    key: PrimaryKey,
+}
+
+fn main() {}
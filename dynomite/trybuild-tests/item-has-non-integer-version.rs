@@ -0,0 +1,11 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    key: String,
+    #[dynomite(version)]
+    version: String,
+}
+
+fn main() {}
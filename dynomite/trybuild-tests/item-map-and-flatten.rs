@@ -0,0 +1,16 @@
+use dynomite_derive::{Attributes, Item};
+
+#[derive(Attributes)]
+struct Bar {
+    value: String,
+}
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    key: String,
+    #[dynomite(map, flatten)]
+    bar: Bar,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    key: String,
+    #[dynomite(sort_key, order = 0)]
+    kind: String,
+    #[dynomite(sort_key, order = 2)]
+    id: String,
+}
+
+fn main() {}
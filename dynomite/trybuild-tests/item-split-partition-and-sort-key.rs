@@ -0,0 +1,10 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(sort_key)]
+    #[dynomite(partition_key)]
+    key: String,
+}
+
+fn main() {}
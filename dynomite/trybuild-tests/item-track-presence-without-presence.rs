@@ -0,0 +1,11 @@
+use dynomite_derive::Item;
+
+#[derive(Item)]
+struct Foo {
+    #[dynomite(partition_key)]
+    key: String,
+    #[dynomite(default, track_presence)]
+    count: u32,
+}
+
+fn main() {}